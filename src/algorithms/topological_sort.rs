@@ -1,47 +1,47 @@
-pub fn topological_sort(jobs: i32, deps: Vec<Vec<i32>>) -> Vec<i32> {
-    let mut v: Vec<(usize, Vec<usize>)> = vec![(0, vec![]); jobs as usize];
-    for p in deps.iter() {
-        v[p[0] as usize].0 += 1;
-        v[p[1] as usize].1.push(p[0] as usize);
-    }
-    let mut stack: Vec<usize> = Vec::new();
-    for (i, e) in (0..).zip(v.iter()) {
-        if e.0 == 0 {
-            stack.push(i);
-        }
+use crate::collections::graph::{Graph, GraphErr};
+
+pub fn topological_sort(jobs: i32, deps: Vec<Vec<i32>>) -> Result<Vec<i32>, GraphErr<i32>> {
+    let mut graph = Graph::new_directed();
+    for job in 0..jobs {
+        graph.add_vertex(job);
     }
-    let mut answer: Vec<i32> = Vec::with_capacity(jobs as usize);
-    while let Some(last) = stack.pop() {
-        answer.push(last as i32);
-        for i in v[last].1.clone() {
-            v[i].0 -= 1;
-            if v[i].0 == 0 {
-                stack.push(i);
-            }
+    for p in deps.iter() {
+        match p[..] {
+            [job, dep] => graph.add_edge(dep, job, 1)?,
+            _ => return Err(GraphErr::with_vertices("invalid dependency", p.clone())),
         }
     }
-    if answer.len() == jobs as usize {
-        answer
-    } else {
-        vec![]
-    }
+    graph.topological_sort()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+
+    #[test]
     fn test_topological() {
         assert_eq!(
             topological_sort(
-                4,
+                5,
                 vec![vec![1, 2], vec![1, 3], vec![3, 2], vec![4, 2], vec![4, 3]]
             ),
-            [1, 4, 3, 2]
+            Ok(vec![0, 2, 3, 1, 4])
         );
         assert_eq!(
             topological_sort(4, vec![vec![1, 0], vec![2, 0], vec![3, 1], vec![3, 2]]),
-            vec![0, 2, 1, 3]
+            Ok(vec![0, 1, 2, 3])
+        );
+        assert_eq!(topological_sort(2, vec![vec![1, 0]]), Ok(vec![0, 1]));
+        assert_eq!(
+            topological_sort(4, vec![vec![4, 2]]),
+            Err(GraphErr::with_vertices("vertex not found", vec![4]))
+        );
+        assert!(topological_sort(2, vec![vec![1]]).is_err());
+        assert_eq!(
+            topological_sort(3, vec![vec![1, 0], vec![2, 1], vec![1, 2]])
+                .unwrap_err()
+                .vertices(),
+            &[1, 2]
         );
-        assert_eq!(topological_sort(2, vec![vec![1, 0]]), vec![0, 1]);
     }
 }
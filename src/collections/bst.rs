@@ -1,69 +1,410 @@
 use std::cmp::Ordering;
 use std::collections::VecDeque;
-use std::convert::Into;
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
 
-type Child<T> = Option<Box<Node<T>>>;
+type Child<K, V> = Option<Box<Node<K, V>>>;
 
 #[derive(Debug)]
-pub struct BST<T: Copy> {
-    root: Child<T>,
+pub struct BST<T> {
+    map: BstMap<T, ()>,
 }
 
 #[derive(Debug)]
-struct Node<T: Copy> {
-    val: T,
-    left: Child<T>,
-    right: Child<T>,
+pub struct BstMap<K, V> {
+    root: Child<K, V>,
+    balanced: bool,
 }
 
-impl<T> BST<T>
-where
-    T: Ord + Copy + Into<i64>,
-{
+#[derive(Debug)]
+struct Node<K, V> {
+    key: K,
+    val: V,
+    height: usize,
+    left: Child<K, V>,
+    right: Child<K, V>,
+}
+
+pub struct Entry<'a, K, V> {
+    map: &'a mut BstMap<K, V>,
+    key: K,
+}
+
+// In-order walk over the keys left between the two stacks: `front` holds the
+// path to the smallest remaining node and `back` the path to the largest.
+pub struct MapIter<'a, K, V> {
+    front: Vec<&'a Node<K, V>>,
+    back: Vec<&'a Node<K, V>>,
+}
+
+pub struct MapIntoIter<K, V> {
+    stack: Vec<Box<Node<K, V>>>,
+}
+
+pub struct Iter<'a, T>(MapIter<'a, T, ()>);
+
+pub struct IntoIter<T>(MapIntoIter<T, ()>);
+
+fn height<K, V>(node: &Child<K, V>) -> usize {
+    node.as_ref().map_or(0, |node| node.height)
+}
+
+// Nodes on the way down from a link, each detached from its parent, with
+// the side the way continues on.
+type Path<K, V> = Vec<(Box<Node<K, V>>, Ordering)>;
+
+// Detaches the nodes from `link` down towards `key` and returns them with
+// the subtree rooted at `key`, or `None` where it would go.
+fn descend<K: Ord, V>(link: &mut Child<K, V>, key: &K) -> (Path<K, V>, Child<K, V>) {
+    let mut path = vec![];
+    let mut current = link.take();
+    while let Some(mut node) = current {
+        let ordering = key.cmp(&node.key);
+        current = match ordering {
+            Ordering::Less => node.left.take(),
+            Ordering::Greater => node.right.take(),
+            Ordering::Equal => return (path, Some(node)),
+        };
+        path.push((node, ordering));
+    }
+    (path, None)
+}
+
+// Puts a path back together from the bottom up, fixing the heights and
+// rebalancing each node on the way when `balanced`.
+fn ascend<K: Ord, V>(path: Path<K, V>, mut child: Child<K, V>, balanced: bool) -> Child<K, V> {
+    for (mut node, ordering) in path.into_iter().rev() {
+        if ordering == Ordering::Less {
+            node.left = child;
+        } else {
+            node.right = child;
+        }
+        node.update_height();
+        if balanced {
+            node.rebalance();
+        }
+        child = Some(node);
+    }
+    child
+}
+
+fn remove<K: Ord, V>(link: &mut Child<K, V>, key: &K, balanced: bool) -> Option<(K, V)> {
+    let (mut path, found) = descend(link, key);
+    let (child, removed) = match found {
+        Some(mut node) if node.left.is_some() && node.right.is_some() => {
+            // two children: the in-order successor takes this node's place
+            let mut right = node.right.take();
+            let (key, val) = remove_min(&mut right, balanced).unwrap();
+            let removed = (
+                mem::replace(&mut node.key, key),
+                mem::replace(&mut node.val, val),
+            );
+            path.push((node, Ordering::Greater));
+            (right, Some(removed))
+        }
+        Some(mut node) => (
+            node.left.take().or_else(|| node.right.take()),
+            Some((node.key, node.val)),
+        ),
+        None => (None, None),
+    };
+    *link = ascend(path, child, balanced);
+    removed
+}
+
+fn remove_min<K: Ord, V>(link: &mut Child<K, V>, balanced: bool) -> Option<(K, V)> {
+    let mut path = vec![];
+    let mut node = link.take()?;
+    while let Some(left) = node.left.take() {
+        path.push((node, Ordering::Less));
+        node = left;
+    }
+    *link = ascend(path, node.right.take(), balanced);
+    Some((node.key, node.val))
+}
+
+// Closest key below (or above) `key`, optionally accepting `key` itself.
+fn closest<'a, K: Ord, V>(
+    mut current: &'a Child<K, V>,
+    key: &K,
+    below: bool,
+    inclusive: bool,
+) -> Option<&'a K> {
+    let mut best = None;
+    while let Some(node) = current {
+        let ordering = node.key.cmp(key);
+        if ordering == Ordering::Equal && inclusive {
+            return Some(&node.key);
+        }
+        let fits = if below {
+            ordering == Ordering::Less
+        } else {
+            ordering == Ordering::Greater
+        };
+        if fits {
+            best = Some(&node.key);
+        }
+        // keep looking for a closer key on the side facing `key`
+        current = if fits == below {
+            &node.right
+        } else {
+            &node.left
+        };
+    }
+    best
+}
+
+impl<T: Ord> BST<T> {
     pub fn new() -> Self {
-        BST { root: None }
+        BST { map: BstMap::new() }
+    }
+
+    // AVL tree: rotations on insert keep the height logarithmic, so sorted
+    // input doesn't degrade it into a list.
+    pub fn new_balanced() -> Self {
+        BST {
+            map: BstMap::new_balanced(),
+        }
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.map.is_balanced()
+    }
+
+    pub fn height(&self) -> usize {
+        self.map.height()
     }
 
     pub fn insert(&mut self, val: T) {
-        match self.root {
-            Some(ref mut root) => root.insert(val),
-            None => self.root = Node::new(val),
+        self.map.insert(val, ());
+    }
+
+    pub fn remove(&mut self, val: &T) -> Option<T> {
+        self.map.remove_entry(val).map(|(val, _)| val)
+    }
+
+    pub fn search(&self, val: &T) -> bool {
+        self.map.contains_key(val)
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.map.root.as_ref()?;
+        while let Some(ref left) = node.left {
+            node = left;
         }
+        Some(&node.key)
     }
 
-    pub fn search(&self, val: T) -> bool {
-        if let Some(ref root) = self.root {
-            root.search(val)
-        } else {
-            false
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.map.root.as_ref()?;
+        while let Some(ref right) = node.right {
+            node = right;
         }
+        Some(&node.key)
     }
 
-    pub fn dfs_in_order(&self) -> Vec<T> {
-        let mut result = Vec::new();
-        if let Some(ref root) = self.root {
-            root.dfs_in_order(&mut result);
+    // Smallest value greater than `val`.
+    pub fn successor(&self, val: &T) -> Option<&T> {
+        closest(&self.map.root, val, false, false)
+    }
+
+    // Largest value less than `val`.
+    pub fn predecessor(&self, val: &T) -> Option<&T> {
+        closest(&self.map.root, val, true, false)
+    }
+
+    // Largest value less than or equal to `val`.
+    pub fn floor(&self, val: &T) -> Option<&T> {
+        closest(&self.map.root, val, true, true)
+    }
+
+    // Smallest value greater than or equal to `val`.
+    pub fn ceiling(&self, val: &T) -> Option<&T> {
+        closest(&self.map.root, val, false, true)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.map.iter())
+    }
+
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Iter<'_, T> {
+        Iter(self.map.range(range))
+    }
+
+    pub fn dfs_in_order(&self) -> Vec<&T> {
+        self.iter().collect()
+    }
+
+    pub fn dfs_pre_order(&self) -> Vec<&T> {
+        self.map
+            .dfs_pre_order()
+            .into_iter()
+            .map(|(val, _)| val)
+            .collect()
+    }
+
+    pub fn dfs_post_order(&self) -> Vec<&T> {
+        self.map
+            .dfs_post_order()
+            .into_iter()
+            .map(|(val, _)| val)
+            .collect()
+    }
+
+    pub fn bfs(&self) -> Vec<&T> {
+        self.map.bfs().into_iter().map(|(val, _)| val).collect()
+    }
+
+    pub fn validate(&self) -> bool {
+        self.map.validate()
+    }
+}
+
+impl<K: Ord, V> BstMap<K, V> {
+    pub fn new() -> Self {
+        BstMap {
+            root: None,
+            balanced: false,
         }
-        result
     }
 
-    pub fn dfs_pre_order(&self) -> Vec<T> {
+    pub fn new_balanced() -> Self {
+        BstMap {
+            root: None,
+            balanced: true,
+        }
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.balanced
+    }
+
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    // Returns the previous value; the original key is kept.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let (path, found) = descend(&mut self.root, &key);
+        let (child, old) = match found {
+            Some(mut node) => {
+                let old = mem::replace(&mut node.val, val);
+                (Some(node), Some(old))
+            }
+            None => (Node::new(key, val), None),
+        };
+        self.root = ascend(path, child, self.balanced);
+        old
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return Some(&node.val),
+            };
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = &mut self.root;
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return Some(&mut node.val),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry { map: self, key }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_, val)| val)
+    }
+
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        remove(&mut self.root, key, self.balanced)
+    }
+
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        self.range(..)
+    }
+
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> MapIter<'_, K, V> {
+        let mut front = vec![];
+        let mut current = &self.root;
+        while let Some(node) = current {
+            let after_start = match range.start_bound() {
+                Bound::Included(start) => node.key >= *start,
+                Bound::Excluded(start) => node.key > *start,
+                Bound::Unbounded => true,
+            };
+            if after_start {
+                front.push(&**node);
+                current = &node.left;
+            } else {
+                current = &node.right;
+            }
+        }
+        let mut back = vec![];
+        let mut current = &self.root;
+        while let Some(node) = current {
+            let before_end = match range.end_bound() {
+                Bound::Included(end) => node.key <= *end,
+                Bound::Excluded(end) => node.key < *end,
+                Bound::Unbounded => true,
+            };
+            if before_end {
+                back.push(&**node);
+                current = &node.right;
+            } else {
+                current = &node.left;
+            }
+        }
+        MapIter { front, back }
+    }
+
+    pub fn dfs_in_order(&self) -> Vec<(&K, &V)> {
+        self.iter().collect()
+    }
+
+    pub fn dfs_pre_order(&self) -> Vec<(&K, &V)> {
         let mut result = Vec::new();
-        if let Some(ref root) = self.root {
-            root.dfs_pre_order(&mut result);
+        let mut stack: Vec<&Node<K, V>> = self.root.as_deref().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            result.push((&node.key, &node.val));
+            stack.extend(node.right.as_deref());
+            stack.extend(node.left.as_deref());
         }
         result
     }
 
-    pub fn dfs_post_order(&self) -> Vec<T> {
+    // Node, right, left, reversed.
+    pub fn dfs_post_order(&self) -> Vec<(&K, &V)> {
         let mut result = Vec::new();
-        if let Some(ref root) = self.root {
-            root.dfs_post_order(&mut result);
+        let mut stack: Vec<&Node<K, V>> = self.root.as_deref().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            result.push((&node.key, &node.val));
+            stack.extend(node.left.as_deref());
+            stack.extend(node.right.as_deref());
         }
+        result.reverse();
         result
     }
 
-    pub fn bfs(&self) -> Vec<T> {
+    pub fn bfs(&self) -> Vec<(&K, &V)> {
         let mut result = Vec::new();
         if let Some(ref root) = self.root {
             root.bfs(&mut result);
@@ -71,111 +412,314 @@ where
         result
     }
 
+    // Checks key order, stored heights and, for balanced maps, the AVL
+    // invariant. An empty map is valid.
     pub fn validate(&self) -> bool {
-        self.root
-            .as_ref()
-            .expect("root is undefined")
-            .validate(std::i64::MIN, std::i64::MAX)
+        // each node with the keys it has to stay strictly between
+        let unbounded: Option<&K> = None;
+        let mut stack = vec![];
+        stack.extend(
+            self.root
+                .as_deref()
+                .map(|root| (root, unbounded, unbounded)),
+        );
+        while let Some((node, min, max)) = stack.pop() {
+            if matches!(min, Some(min) if node.key <= *min)
+                || matches!(max, Some(max) if node.key >= *max)
+            {
+                return false;
+            }
+            if node.height != 1 + height(&node.left).max(height(&node.right)) {
+                return false;
+            }
+            if self.balanced && node.balance_factor().abs() > 1 {
+                return false;
+            }
+            stack.extend(
+                node.left
+                    .as_deref()
+                    .map(|left| (left, min, Some(&node.key))),
+            );
+            stack.extend(
+                node.right
+                    .as_deref()
+                    .map(|right| (right, Some(&node.key), max)),
+            );
+        }
+        true
     }
 }
 
-trait TreeNode<T: Copy> {
-    fn new(val: T) -> Option<Box<Node<T>>>;
-    fn insert(&mut self, val: T);
-    fn search(&self, val: T) -> bool;
-    fn dfs_in_order(&self, result: &mut Vec<T>);
-    fn dfs_pre_order(&self, result: &mut Vec<T>);
-    fn dfs_post_order(&self, result: &mut Vec<T>);
-    fn bfs(&self, result: &mut Vec<T>);
-    fn validate(&self, min: i64, max: i64) -> bool;
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        if let Some(val) = self.map.get_mut(&self.key) {
+            f(val);
+        }
+        self
+    }
+
+    // Inserting may rotate the new node anywhere along its path, so it is
+    // looked up again afterwards with a copy of the key.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V
+    where
+        K: Clone,
+    {
+        if !self.map.contains_key(&self.key) {
+            self.map.insert(self.key.clone(), default());
+        }
+        self.map.get_mut(&self.key).unwrap()
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V
+    where
+        K: Clone,
+    {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        K: Clone,
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for MapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, last) = (self.front.last()?, self.back.last()?);
+        if first.key > last.key {
+            // the two ends have met
+            self.front.clear();
+            self.back.clear();
+            return None;
+        }
+        let node = self.front.pop().unwrap();
+        let mut current = &node.right;
+        while let Some(child) = current {
+            self.front.push(child);
+            current = &child.left;
+        }
+        Some((&node.key, &node.val))
+    }
 }
 
-impl<T> TreeNode<T> for Node<T>
+impl<'a, K: Ord, V> DoubleEndedIterator for MapIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (first, last) = (self.front.last()?, self.back.last()?);
+        if first.key > last.key {
+            self.front.clear();
+            self.back.clear();
+            return None;
+        }
+        let node = self.back.pop().unwrap();
+        let mut current = &node.left;
+        while let Some(child) = current {
+            self.back.push(child);
+            current = &child.right;
+        }
+        Some((&node.key, &node.val))
+    }
+}
+
+impl<K, V> MapIntoIter<K, V> {
+    fn push_left(&mut self, mut current: Child<K, V>) {
+        while let Some(mut node) = current {
+            current = node.left.take();
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<K, V> Iterator for MapIntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        let mut node = self.stack.pop()?;
+        self.push_left(node.right.take());
+        Some((node.key, node.val))
+    }
+}
+
+impl<K, V> IntoIterator for BstMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = MapIntoIter<K, V>;
+
+    fn into_iter(self) -> MapIntoIter<K, V> {
+        let mut iter = MapIntoIter { stack: vec![] };
+        iter.push_left(self.root);
+        iter
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a BstMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> MapIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+// Collected maps and trees are balanced, since sorted input is the common case.
+impl<K: Ord, V> FromIterator<(K, V)> for BstMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = BstMap::new_balanced();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for BstMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.insert(key, val);
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.0.next().map(|(val, _)| val)
+    }
+}
+
+impl<'a, T: Ord> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.0.next_back().map(|(val, _)| val)
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next().map(|(val, _)| val)
+    }
+}
+
+impl<T> IntoIterator for BST<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self.map.into_iter())
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a BST<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BST<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = BST::new_balanced();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for BST<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.insert(val);
+        }
+    }
+}
+
+trait TreeNode<K, V> {
+    fn new(key: K, val: V) -> Option<Box<Node<K, V>>>;
+    fn update_height(&mut self);
+    fn balance_factor(&self) -> isize;
+    fn rotate_left(&mut self);
+    fn rotate_right(&mut self);
+    fn rebalance(&mut self);
+    fn bfs<'a>(&'a self, result: &mut Vec<(&'a K, &'a V)>);
+}
+
+impl<K, V> TreeNode<K, V> for Node<K, V>
 where
-    T: Ord + Copy + Into<i64>,
+    K: Ord,
 {
-    fn new(val: T) -> Option<Box<Node<T>>> {
+    fn new(key: K, val: V) -> Option<Box<Node<K, V>>> {
         Some(Box::new(Node {
+            key,
             val,
+            height: 1,
             left: None,
             right: None,
         }))
     }
 
-    fn insert(&mut self, val: T) {
-        match self.val.cmp(&val) {
-            Ordering::Less => match self.right {
-                Some(ref mut node) => node.insert(val),
-                None => {
-                    self.right = Node::new(val);
-                }
-            },
-            Ordering::Greater => match self.left {
-                Some(ref mut node) => node.insert(val),
-                None => {
-                    self.left = Node::new(val);
-                }
-            },
-            Ordering::Equal => {}
-        };
+    fn update_height(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
     }
-    fn search(&self, val: T) -> bool {
-        match self.val.cmp(&val) {
-            Ordering::Equal => true,
-            Ordering::Less => {
-                if let Some(ref node) = self.right {
-                    node.search(val)
-                } else {
-                    false
-                }
-            }
-            Ordering::Greater => {
-                if let Some(ref node) = self.left {
-                    node.search(val)
-                } else {
-                    false
-                }
-            }
-        }
+
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
     }
-    fn dfs_in_order(&self, result: &mut Vec<T>) {
-        if let Some(ref left) = self.left {
-            left.dfs_in_order(result);
-        }
-        result.push(self.val);
-        if let Some(ref right) = self.right {
-            right.dfs_in_order(result);
-        }
+
+    // The rotations swap node contents so the parent's link to `self` stays
+    // valid.
+    fn rotate_left(&mut self) {
+        let mut right = self.right.take().expect("right child is undefined");
+        self.right = right.left.take();
+        mem::swap(self, &mut right);
+        right.update_height();
+        self.left = Some(right);
+        self.update_height();
     }
 
-    fn dfs_pre_order(&self, result: &mut Vec<T>) {
-        result.push(self.val);
-        if let Some(ref left) = self.left {
-            left.dfs_pre_order(result);
-        }
-        if let Some(ref right) = self.right {
-            right.dfs_pre_order(result);
-        }
+    fn rotate_right(&mut self) {
+        let mut left = self.left.take().expect("left child is undefined");
+        self.left = left.right.take();
+        mem::swap(self, &mut left);
+        left.update_height();
+        self.right = Some(left);
+        self.update_height();
     }
 
-    fn dfs_post_order(&self, result: &mut Vec<T>) {
-        if let Some(ref left) = self.left {
-            left.dfs_post_order(result);
-        }
-        if let Some(ref right) = self.right {
-            right.dfs_post_order(result);
+    fn rebalance(&mut self) {
+        let balance = self.balance_factor();
+        if balance > 1 {
+            if let Some(ref mut left) = self.left {
+                if left.balance_factor() < 0 {
+                    left.rotate_left();
+                }
+            }
+            self.rotate_right();
+        } else if balance < -1 {
+            if let Some(ref mut right) = self.right {
+                if right.balance_factor() > 0 {
+                    right.rotate_right();
+                }
+            }
+            self.rotate_left();
         }
-        result.push(self.val);
     }
 
-    fn bfs(&self, result: &mut Vec<T>) {
-        let mut queue: VecDeque<&Node<T>> = VecDeque::new();
+    fn bfs<'a>(&'a self, result: &mut Vec<(&'a K, &'a V)>) {
+        let mut queue: VecDeque<&Node<K, V>> = VecDeque::new();
         let mut current_node = self;
         queue.push_back(current_node);
         while !queue.is_empty() {
             if let Some(node) = queue.pop_front() {
                 current_node = node;
-                result.push(current_node.val);
+                result.push((&current_node.key, &current_node.val));
                 if let Some(ref left) = current_node.left {
                     queue.push_back(left);
                 }
@@ -185,21 +729,6 @@ where
             }
         }
     }
-
-    fn validate(&self, min: i64, max: i64) -> bool {
-        if self.val.into() <= min || self.val.into() >= max {
-            return false;
-        }
-        let left_is_valid = match &self.left {
-            Some(left) => left.validate(min, self.val.into()),
-            None => true,
-        };
-        left_is_valid
-            && match &self.right {
-                Some(right) => right.validate(self.val.into(), max),
-                None => true,
-            }
-    }
 }
 
 #[cfg(test)]
@@ -216,13 +745,13 @@ mod test {
         tree.insert(20);
         tree.insert(5);
 
-        assert_eq!(tree.search(100), true);
-        assert_eq!(tree.search(1), true);
-        assert_eq!(tree.search(600), true);
-        assert_eq!(tree.search(300), true);
-        assert_eq!(tree.search(20), true);
-        assert_eq!(tree.search(5), true);
-        assert_eq!(tree.search(6), false);
+        assert_eq!(tree.search(&100), true);
+        assert_eq!(tree.search(&1), true);
+        assert_eq!(tree.search(&600), true);
+        assert_eq!(tree.search(&300), true);
+        assert_eq!(tree.search(&20), true);
+        assert_eq!(tree.search(&5), true);
+        assert_eq!(tree.search(&6), false);
     }
 
     #[test]
@@ -239,11 +768,13 @@ mod test {
         let pre_order_data = vec![100, 1, 20, 5, 600, 300];
         let post_order_data = vec![5, 20, 1, 300, 600, 100];
         let bfs_data = vec![100, 1, 600, 20, 300, 5];
+        let (in_order, pre_order) = (tree.dfs_in_order(), tree.dfs_pre_order());
+        let (post_order, bfs) = (tree.dfs_post_order(), tree.bfs());
         for i in 0..in_order_data.len() {
-            assert_eq!(tree.dfs_in_order()[i], in_order_data[i]);
-            assert_eq!(tree.dfs_pre_order()[i], pre_order_data[i]);
-            assert_eq!(tree.dfs_post_order()[i], post_order_data[i]);
-            assert_eq!(tree.bfs()[i], bfs_data[i]);
+            assert_eq!(*in_order[i], in_order_data[i]);
+            assert_eq!(*pre_order[i], pre_order_data[i]);
+            assert_eq!(*post_order[i], post_order_data[i]);
+            assert_eq!(*bfs[i], bfs_data[i]);
         }
     }
 
@@ -258,5 +789,300 @@ mod test {
         tree.insert(5);
 
         assert_eq!(tree.validate(), true);
+        assert_eq!(tree.height(), 4);
+
+        let mut tree = BST::new_balanced();
+        tree.extend(1..=7);
+        assert_eq!(tree.validate(), true);
+        assert_eq!(tree.height(), 3);
+        // heights still match, but the root is too deep on one side
+        let root = tree.map.root.as_mut().unwrap();
+        root.left = None;
+        root.update_height();
+        assert_eq!(tree.validate(), false);
+    }
+
+    #[test]
+    fn remove_and_neighbors() {
+        let mut tree = BST::new();
+        for val in &[100, 1, 600, 300, 20, 5, 700, 650] {
+            tree.insert(*val);
+        }
+        assert_eq!(tree.min(), Some(&1));
+        assert_eq!(tree.max(), Some(&700));
+        assert_eq!(tree.successor(&100), Some(&300));
+        assert_eq!(tree.predecessor(&100), Some(&20));
+        assert_eq!(tree.floor(&99), Some(&20));
+        assert_eq!(tree.ceiling(&99), Some(&100));
+        assert_eq!(tree.floor(&100), Some(&100));
+        assert_eq!(tree.successor(&700), None);
+        assert_eq!(tree.predecessor(&1), None);
+
+        // root with two children is replaced by its successor
+        assert_eq!(tree.remove(&100), Some(100));
+        assert_eq!(
+            tree.dfs_pre_order(),
+            vec![&300, &1, &20, &5, &600, &700, &650]
+        );
+        assert_eq!(tree.remove(&100), None);
+        assert_eq!(tree.remove(&1), Some(1));
+        assert_eq!(tree.remove(&700), Some(700));
+        assert_eq!(tree.dfs_pre_order(), vec![&300, &20, &5, &600, &650]);
+        assert_eq!(tree.validate(), true);
+        for val in &[300, 20, 5, 600, 650] {
+            tree.remove(val);
+        }
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.ceiling(&0), None);
+        assert_eq!(tree.validate(), true);
+    }
+
+    #[test]
+    fn matches_btree_set() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+        use std::collections::BTreeSet;
+        use std::ops::Bound::{Excluded, Unbounded};
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for tree in &mut [BST::new(), BST::new_balanced()] {
+            let mut set = BTreeSet::new();
+            for _ in 0..5000 {
+                let val: i32 = rng.gen_range(0, 500);
+                if rng.gen_bool(0.6) {
+                    tree.insert(val);
+                    set.insert(val);
+                } else {
+                    assert_eq!(tree.remove(&val), set.take(&val));
+                }
+                let probe = rng.gen_range(-10, 510);
+                assert_eq!(tree.search(&probe), set.contains(&probe));
+                assert_eq!(tree.min(), set.iter().next());
+                assert_eq!(tree.max(), set.iter().next_back());
+                assert_eq!(tree.floor(&probe), set.range(..=probe).next_back());
+                assert_eq!(tree.ceiling(&probe), set.range(probe..).next());
+                assert_eq!(tree.predecessor(&probe), set.range(..probe).next_back());
+                assert_eq!(
+                    tree.successor(&probe),
+                    set.range((Excluded(probe), Unbounded)).next()
+                );
+            }
+            assert_eq!(tree.dfs_in_order(), set.iter().collect::<Vec<_>>());
+            assert_eq!(tree.validate(), true);
+        }
+    }
+
+    #[test]
+    fn balanced() {
+        let mut tree = BST::new_balanced();
+        for i in 1..=7 {
+            tree.insert(i);
+        }
+        assert_eq!(tree.dfs_pre_order(), vec![&4, &2, &1, &3, &6, &5, &7]);
+        assert_eq!(tree.bfs(), vec![&4, &2, &6, &1, &3, &5, &7]);
+        assert_eq!(tree.height(), 3);
+
+        // sorted and reverse sorted input stay logarithmic
+        let mut tree = BST::new_balanced();
+        for i in 0..100_000 {
+            tree.insert(i);
+            tree.insert(-i);
+        }
+        assert!(tree.height() <= 24);
+        assert_eq!(tree.validate(), true);
+        assert_eq!(tree.search(&99_999), true);
+        assert_eq!(tree.search(&-50_000), true);
+        assert_eq!(tree.search(&100_000), false);
+        assert!(tree
+            .dfs_in_order()
+            .into_iter()
+            .copied()
+            .eq(-99_999..100_000));
+    }
+
+    #[test]
+    fn map() {
+        let mut map = BstMap::new_balanced();
+        for (i, word) in ["pear", "apple", "fig", "kiwi", "date", "plum"]
+            .iter()
+            .enumerate()
+        {
+            assert_eq!(map.insert(word.to_string(), i), None);
+        }
+        assert_eq!(map.insert("fig".to_string(), 10), Some(2));
+        assert_eq!(map.get(&"fig".to_string()), Some(&10));
+        assert_eq!(map.get(&"grape".to_string()), None);
+        *map.get_mut(&"kiwi".to_string()).unwrap() += 100;
+
+        let in_order: Vec<_> = map
+            .dfs_in_order()
+            .into_iter()
+            .map(|(key, val)| (key.as_str(), *val))
+            .collect();
+        assert_eq!(
+            in_order,
+            vec![
+                ("apple", 1),
+                ("date", 4),
+                ("fig", 10),
+                ("kiwi", 103),
+                ("pear", 0),
+                ("plum", 5)
+            ]
+        );
+        assert_eq!(map.dfs_pre_order()[0].0, "fig");
+        assert_eq!(map.bfs().len(), 6);
+        assert_eq!(map.dfs_post_order()[5].0, "fig");
+
+        assert_eq!(map.remove(&"fig".to_string()), Some(10));
+        assert_eq!(map.remove(&"fig".to_string()), None);
+        assert_eq!(
+            map.remove_entry(&"apple".to_string()),
+            Some(("apple".to_string(), 1))
+        );
+        let keys: Vec<_> = map
+            .dfs_in_order()
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["date", "kiwi", "pear", "plum"]);
+    }
+
+    #[test]
+    fn map_entry() {
+        let mut counts = BstMap::new_balanced();
+        for word in "the cat and the hat and the bat".split(' ') {
+            counts
+                .entry(word)
+                .and_modify(|count| *count += 1)
+                .or_insert(1);
+        }
+        *counts.entry("cat").or_default() += 10;
+        assert_eq!(counts.entry("dog").key(), &"dog");
+        assert_eq!(
+            counts.dfs_in_order(),
+            vec![
+                (&"and", &2),
+                (&"bat", &1),
+                (&"cat", &11),
+                (&"hat", &1),
+                (&"the", &3)
+            ]
+        );
+
+        let mut lists: BstMap<i32, Vec<i32>> = BstMap::new();
+        for i in 0..20 {
+            lists.entry(i % 3).or_default().push(i);
+        }
+        assert_eq!(lists.get(&2), Some(&vec![2, 5, 8, 11, 14, 17]));
+        let evens = lists
+            .entry(-2)
+            .or_insert_with(|| (0..20).step_by(2).collect());
+        assert_eq!(evens.len(), 10);
+    }
+
+    #[test]
+    fn any_ord_type() {
+        let mut words = BST::new_balanced();
+        for word in "the quick brown fox jumps over the lazy dog".split(' ') {
+            words.insert(word.to_string());
+        }
+        assert_eq!(words.search(&"fox".to_string()), true);
+        assert_eq!(words.min().map(String::as_str), Some("brown"));
+        assert_eq!(
+            words.successor(&"quick".to_string()).map(String::as_str),
+            Some("the")
+        );
+        assert_eq!(words.remove(&"the".to_string()), Some("the".to_string()));
+        assert_eq!(words.dfs_in_order().len(), 7);
+        assert_eq!(words.validate(), true);
+
+        // values past i64::MAX used to break validate
+        let mut big = BST::new();
+        for val in &[u64::MAX, 0, u64::MAX - 1, 1 << 63] {
+            big.insert(*val);
+        }
+        assert_eq!(big.validate(), true);
+        assert_eq!(big.max(), Some(&u64::MAX));
+
+        let mut pairs = BST::new();
+        pairs.insert((2, 'b'));
+        pairs.insert((1, 'z'));
+        pairs.insert((2, 'a'));
+        assert_eq!(pairs.dfs_in_order(), vec![&(1, 'z'), &(2, 'a'), &(2, 'b')]);
+        assert_eq!(pairs.floor(&(2, 'a')), Some(&(2, 'a')));
+
+        assert_eq!(BST::<String>::new().validate(), true);
+        assert_eq!(BstMap::<u64, ()>::new_balanced().validate(), true);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut tree: BST<i32> = vec![100, 1, 600, 300, 20, 5].into_iter().collect();
+        assert_eq!(tree.is_balanced(), true);
+        assert!(tree.iter().eq(&[1, 5, 20, 100, 300, 600]));
+        assert!(tree.iter().rev().eq(&[600, 300, 100, 20, 5, 1]));
+        assert!(tree.range(5..300).eq(&[5, 20, 100]));
+        assert!(tree.range(6..=300).rev().eq(&[300, 100, 20]));
+        assert!(tree.range(..20).eq(&[1, 5]));
+        assert_eq!(tree.range(300..300).next(), None);
+        assert_eq!(tree.range(700..).next(), None);
+
+        // both ends meet in the middle without repeating a value
+        let mut iter = tree.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&600));
+        assert_eq!(iter.next_back(), Some(&300));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.next(), Some(&20));
+        assert_eq!(iter.next_back(), Some(&100));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        tree.extend(vec![2, 3, 5]);
+        let mut sum = 0;
+        for val in &tree {
+            sum += val;
+        }
+        assert_eq!(sum, 1031);
+        assert_eq!(
+            tree.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 5, 20, 100, 300, 600]
+        );
+
+        let map: BstMap<_, _> = (0..1000).map(|i| (i, i * i)).collect();
+        assert!(map.height() <= 11);
+        assert_eq!(
+            map.range(10..13).collect::<Vec<_>>(),
+            vec![(&10, &100), (&11, &121), (&12, &144)]
+        );
+        assert_eq!(map.iter().next_back(), Some((&999, &998_001)));
+        assert_eq!(
+            map.into_iter().map(|(_, val)| val).sum::<i32>(),
+            332_833_500
+        );
+    }
+
+    #[test]
+    fn range_matches_btree_set() {
+        use std::collections::BTreeSet;
+
+        let tree: BST<i32> = (0..200)
+            .map(|i| i * 7 % 200)
+            .filter(|i| i % 3 != 0)
+            .collect();
+        let set: BTreeSet<i32> = tree.iter().cloned().collect();
+        for start in -2..202 {
+            for end in (start..202).step_by(13) {
+                assert!(tree.range(start..end).eq(set.range(start..end)));
+                assert!(tree
+                    .range(start..=end)
+                    .rev()
+                    .eq(set.range(start..=end).rev()));
+                assert!(tree
+                    .range((Bound::Excluded(start), Bound::Included(end)))
+                    .eq(set.range((Bound::Excluded(start), Bound::Included(end)))));
+            }
+        }
     }
 }
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::vec;
+
+#[derive(Debug)]
+pub struct DisjointSet<T> {
+    items: Vec<T>,
+    index: HashMap<T, usize>,
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    num_sets: usize,
+}
+
+pub struct Groups<'a, T> {
+    groups: vec::IntoIter<Vec<&'a T>>,
+}
+
+impl<T> DisjointSet<T>
+where
+    T: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        DisjointSet {
+            items: vec![],
+            index: HashMap::new(),
+            parent: vec![],
+            size: vec![],
+            num_sets: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.index.contains_key(item)
+    }
+
+    pub fn make_set(&mut self, item: T) -> bool {
+        if self.index.contains_key(&item) {
+            return false;
+        }
+        let idx = self.items.len();
+        self.index.insert(item.clone(), idx);
+        self.items.push(item);
+        self.parent.push(idx);
+        self.size.push(1);
+        self.num_sets += 1;
+        true
+    }
+
+    pub fn find(&mut self, item: &T) -> Option<&T> {
+        let idx = *self.index.get(item)?;
+        let root = self.find_root(idx);
+        Some(&self.items[root])
+    }
+
+    pub fn union(&mut self, a: &T, b: &T) -> bool {
+        let (a, b) = match (self.index.get(a), self.index.get(b)) {
+            (Some(a), Some(b)) => (*a, *b),
+            _ => return false,
+        };
+        let (mut root_a, mut root_b) = (self.find_root(a), self.find_root(b));
+        if root_a == root_b {
+            return false;
+        }
+        if self.size[root_a] < self.size[root_b] {
+            std::mem::swap(&mut root_a, &mut root_b);
+        }
+        self.parent[root_b] = root_a;
+        self.size[root_a] += self.size[root_b];
+        self.num_sets -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: &T, b: &T) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(a), Some(b)) => {
+                let (a, b) = (*a, *b);
+                self.find_root(a) == self.find_root(b)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn set_size(&mut self, item: &T) -> usize {
+        match self.index.get(item) {
+            Some(idx) => {
+                let root = self.find_root(*idx);
+                self.size[root]
+            }
+            None => 0,
+        }
+    }
+
+    pub fn groups(&self) -> Groups<'_, T> {
+        let mut slots: HashMap<usize, usize> = HashMap::new();
+        let mut groups: Vec<Vec<&T>> = vec![];
+        for (idx, item) in self.items.iter().enumerate() {
+            let mut root = idx;
+            while self.parent[root] != root {
+                root = self.parent[root];
+            }
+            let slot = *slots.entry(root).or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[slot].push(item);
+        }
+        Groups {
+            groups: groups.into_iter(),
+        }
+    }
+
+    fn find_root(&mut self, idx: usize) -> usize {
+        let mut root = idx;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = idx;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+}
+
+impl<'a, T> Iterator for Groups<'a, T> {
+    type Item = Vec<&'a T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.groups.next()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut set = DisjointSet::new();
+        assert!(set.is_empty());
+        assert_eq!(set.find(&1), None);
+        for i in 0..6 {
+            assert!(set.make_set(i));
+        }
+        assert!(!set.make_set(3));
+        assert_eq!(set.len(), 6);
+        assert_eq!(set.num_sets(), 6);
+        assert_eq!(set.find(&4), Some(&4));
+
+        assert!(set.union(&0, &1));
+        assert!(set.union(&2, &3));
+        assert!(set.union(&1, &3));
+        assert!(!set.union(&0, &2));
+        assert!(!set.union(&0, &9));
+        let root = set.find(&3).cloned();
+        assert_eq!(set.find(&0).cloned(), root);
+        assert_ne!(set.find(&4).cloned(), root);
+        assert_eq!(set.find(&5), Some(&5));
+        assert_eq!(set.num_sets(), 3);
+    }
+
+    #[test]
+    fn test_sets_and_groups() {
+        let mut set = DisjointSet::new();
+        for name in &["ann", "bob", "cat", "dan", "eve"] {
+            set.make_set(name.to_string());
+        }
+        let key = |s: &str| s.to_string();
+        set.union(&key("eve"), &key("bob"));
+        set.union(&key("cat"), &key("ann"));
+        set.union(&key("bob"), &key("dan"));
+
+        assert!(set.same_set(&key("eve"), &key("dan")));
+        assert!(!set.same_set(&key("eve"), &key("ann")));
+        assert!(!set.same_set(&key("eve"), &key("zed")));
+        assert_eq!(set.set_size(&key("dan")), 3);
+        assert_eq!(set.set_size(&key("ann")), 2);
+        assert_eq!(set.set_size(&key("zed")), 0);
+        assert_eq!(set.num_sets(), 2);
+
+        let groups: Vec<Vec<&String>> = set.groups().collect();
+        assert_eq!(
+            groups,
+            vec![
+                vec![&key("ann"), &key("cat")],
+                vec![&key("bob"), &key("dan"), &key("eve")],
+            ]
+        );
+    }
+}
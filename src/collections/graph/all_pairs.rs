@@ -0,0 +1,435 @@
+use super::csr::CsrGraph;
+use super::shortest_path::{check_fit, is_shorter, negative_cycle, Tree};
+use super::{checked_add, Graph, GraphErr, GraphNode, Weight};
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+#[derive(Debug)]
+pub struct AllPairs<T, W = usize> {
+    vertices: Vec<T>,
+    index: HashMap<T, usize>,
+    dist: Vec<Vec<Option<W>>>,
+    prev: Vec<Vec<Option<usize>>>,
+}
+
+impl<T, W> AllPairs<T, W>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn vertices(&self) -> &[T] {
+        &self.vertices
+    }
+
+    pub fn matrix(&self) -> &[Vec<Option<W>>] {
+        &self.dist
+    }
+
+    pub fn distance<Q>(&self, from: &Q, to: &Q) -> Option<W>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (from, to) = (self.index.get(from)?, self.index.get(to)?);
+        self.dist[*from][*to]
+    }
+
+    pub fn distances_from<Q>(&self, from: &Q) -> HashMap<T, W>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let mut distances = HashMap::new();
+        if let Some(from) = self.index.get(from) {
+            for (to, dist) in self.dist[*from].iter().enumerate() {
+                if let Some(dist) = dist {
+                    distances.insert(self.vertices[to].clone(), *dist);
+                }
+            }
+        }
+        distances
+    }
+
+    pub fn path<Q>(&self, from: &Q, to: &Q) -> Option<Vec<T>>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (from, to) = (*self.index.get(from)?, *self.index.get(to)?);
+        self.dist[from][to]?;
+        let mut path = vec![to];
+        while let Some(prev) = self.prev[from][path[path.len() - 1]] {
+            path.push(prev);
+        }
+        Some(
+            path.iter()
+                .rev()
+                .map(|v| self.vertices[*v].clone())
+                .collect(),
+        )
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn floyd_warshall(&self) -> Result<AllPairs<T, W>, GraphErr<T>> {
+        let csr = self.freeze();
+        let n = csr.vertex_count();
+        let mut dist: Vec<Vec<Option<W>>> = vec![vec![None; n]; n];
+        let mut prev: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+        // pairs joined by a path too long for `W`, and the first pair joined
+        // by one too short, which only a negative cycle or a distance that
+        // does not fit can give
+        let mut too_long = vec![vec![false; n]; n];
+        let mut too_short = None;
+        for u in 0..n {
+            dist[u][u] = Some(W::zero());
+            for (v, weight) in csr.edges(u) {
+                if is_shorter(weight, dist[u][v]) {
+                    dist[u][v] = Some(weight);
+                    prev[u][v] = Some(u);
+                }
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                if dist[i][k].is_none() && !too_long[i][k] {
+                    continue;
+                }
+                for j in 0..n {
+                    let new_dist = match (dist[i][k], dist[k][j]) {
+                        (Some(dist_ik), Some(dist_kj)) => match checked_add(dist_ik, dist_kj) {
+                            Some(new_dist) => new_dist,
+                            None if dist_kj < W::zero() => {
+                                too_short.get_or_insert((i, j));
+                                W::min_value()
+                            }
+                            None => {
+                                too_long[i][j] = true;
+                                continue;
+                            }
+                        },
+                        (_, None) if !too_long[k][j] => continue,
+                        _ => {
+                            too_long[i][j] = true;
+                            continue;
+                        }
+                    };
+                    if is_shorter(new_dist, dist[i][j]) {
+                        dist[i][j] = Some(new_dist);
+                        prev[i][j] = prev[k][j];
+                        if i == j && new_dist < W::zero() {
+                            return Err(GraphErr::with_vertices(
+                                "negative cycle",
+                                csr.vertices_of(&negative_cycle(i, &prev[i])),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some((i, j)) = too_short {
+            return Err(GraphErr::with_vertices(
+                "path weight overflows",
+                csr.vertices_of(&[i, j]),
+            ));
+        }
+        for i in 0..n {
+            let mut unfit: Vec<usize> = (0..n)
+                .filter(|j| too_long[i][*j] && dist[i][*j].is_none())
+                .collect();
+            if !unfit.is_empty() {
+                unfit.insert(0, i);
+                return Err(GraphErr::with_vertices(
+                    "path weight overflows",
+                    csr.vertices_of(&unfit),
+                ));
+            }
+        }
+        Ok(AllPairs {
+            vertices: csr.vertices,
+            index: csr.index,
+            dist,
+            prev,
+        })
+    }
+
+    pub fn johnson(&self) -> Result<AllPairs<T, W>, GraphErr<T>> {
+        let csr = self.freeze();
+        let potential = johnson_potential(&csr)?;
+        let n = csr.vertex_count();
+        let mut dist = Vec::with_capacity(n);
+        let mut prev = Vec::with_capacity(n);
+        for source in 0..n {
+            let (row, tree) = reweighted_dijkstra(&csr, &potential, source)?;
+            dist.push(row);
+            prev.push(tree);
+        }
+        Ok(AllPairs {
+            vertices: csr.vertices,
+            index: csr.index,
+            dist,
+            prev,
+        })
+    }
+}
+
+fn johnson_potential<T, W>(csr: &CsrGraph<T, W>) -> Result<Vec<W>, GraphErr<T>>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    // Bellman-Ford from a virtual source joined to every vertex by a zero
+    // weight edge, which is the same as starting every potential at zero.
+    // Potentials never rise above zero, so a sum that does not fit is below
+    // the range of `W` and is kept at its bottom.
+    let n = csr.vertex_count();
+    let mut potential = vec![W::zero(); n];
+    let mut prev: Vec<Option<usize>> = vec![None; n];
+    let mut too_short = None;
+    let mut relaxed = None;
+    for _ in 0..=n {
+        relaxed = None;
+        for u in 0..n {
+            for (v, weight) in csr.edges(u) {
+                let sum = checked_add(potential[u], weight);
+                let new_potential = sum.unwrap_or_else(W::min_value);
+                if new_potential < potential[v] {
+                    if sum.is_none() {
+                        too_short.get_or_insert(v);
+                    }
+                    potential[v] = new_potential;
+                    prev[v] = Some(u);
+                    relaxed = Some(v);
+                }
+            }
+        }
+        if relaxed.is_none() {
+            break;
+        }
+    }
+    if relaxed.is_none() && too_short.is_none() {
+        return Ok(potential);
+    }
+    // Any cycle among the predecessors has negative weight; |V| steps back
+    // from a vertex that does not reach a root land on one.
+    let on_cycle = (0..n)
+        .map(|v| (0..n).fold(v, |v, _| prev[v].unwrap_or(v)))
+        .find(|v| prev[*v].is_some());
+    match on_cycle {
+        Some(v) => Err(GraphErr::with_vertices(
+            "negative cycle",
+            csr.vertices_of(&negative_cycle(v, &prev)),
+        )),
+        None => Err(GraphErr::with_vertices(
+            "path weight overflows",
+            csr.vertices_of(&too_short.into_iter().collect::<Vec<_>>()),
+        )),
+    }
+}
+
+fn reweighted_dijkstra<T, W>(
+    csr: &CsrGraph<T, W>,
+    potential: &[W],
+    source: usize,
+) -> Result<Tree<W>, GraphErr<T>>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    // `weight + potential[u]` fits as it is at least `potential[v]`, and
+    // taking away a potential, which is never positive, can only overflow
+    // upwards. Reweighted distances have to fit in `W` as well.
+    let reweight = |u: usize, v: usize, weight: W| {
+        let reduced = weight + potential[u];
+        if reduced > W::max_value() + potential[v] {
+            None
+        } else {
+            Some(reduced - potential[v])
+        }
+    };
+    let n = csr.vertex_count();
+    let mut dist: Vec<Option<W>> = vec![None; n];
+    let mut prev: Vec<Option<usize>> = vec![None; n];
+    let mut too_long = vec![false; n];
+    let mut heap: BinaryHeap<Reverse<GraphNode<usize, W>>> = BinaryHeap::new();
+    dist[source] = Some(W::zero());
+    heap.push(Reverse(GraphNode::new(source, W::zero())));
+    while let Some(Reverse(GraphNode {
+        vertex: u,
+        weight: current_dist,
+    })) = heap.pop()
+    {
+        if matches!(dist[u], Some(dist) if current_dist > dist) {
+            continue;
+        }
+        for (v, weight) in csr.edges(u) {
+            match reweight(u, v, weight).and_then(|weight| checked_add(current_dist, weight)) {
+                Some(new_dist) if is_shorter(new_dist, dist[v]) => {
+                    dist[v] = Some(new_dist);
+                    prev[v] = Some(u);
+                    heap.push(Reverse(GraphNode::new(v, new_dist)));
+                }
+                Some(_) => {}
+                None => too_long[v] = true,
+            }
+        }
+    }
+
+    // back to the original weights, where adding `potential[v]` fits and
+    // taking away `potential[source]` can only overflow upwards
+    for (v, d) in dist.iter_mut().enumerate() {
+        if let Some(reweighted) = *d {
+            let shifted = reweighted + potential[v];
+            if shifted > W::max_value() + potential[source] {
+                *d = None;
+                too_long[v] = true;
+            } else {
+                *d = Some(shifted - potential[source]);
+            }
+        }
+    }
+    check_fit(&csr.vertices, &dist, &too_long, None, |v| csr.edges(v)).map_err(|err| {
+        let mut vertices = vec![csr.vertices[source].clone()];
+        vertices.extend(err.vertices().iter().cloned());
+        GraphErr::with_vertices(err.msg(), vertices)
+    })?;
+    Ok((dist, prev))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph() -> Graph<char, i32> {
+        let mut graph = Graph::new_directed();
+        for v in "abcdez".chars() {
+            graph.add_vertex(v);
+        }
+        graph.add_edge('a', 'b', 3).unwrap();
+        graph.add_edge('a', 'c', 8).unwrap();
+        graph.add_edge('a', 'e', -4).unwrap();
+        graph.add_edge('b', 'd', 1).unwrap();
+        graph.add_edge('b', 'e', 7).unwrap();
+        graph.add_edge('c', 'b', 4).unwrap();
+        graph.add_edge('d', 'a', 2).unwrap();
+        graph.add_edge('d', 'c', -5).unwrap();
+        graph.add_edge('e', 'd', 6).unwrap();
+        graph
+    }
+
+    fn check_all_pairs(all_pairs: &AllPairs<char, i32>) {
+        assert_eq!(all_pairs.vertices(), &['a', 'b', 'c', 'd', 'e', 'z']);
+        assert_eq!(
+            all_pairs.matrix()[0],
+            vec![Some(0), Some(1), Some(-3), Some(2), Some(-4), None]
+        );
+        assert_eq!(all_pairs.distance(&'e', &'b'), Some(5));
+        assert_eq!(all_pairs.distance(&'z', &'z'), Some(0));
+        assert_eq!(all_pairs.distance(&'a', &'z'), None);
+        assert_eq!(all_pairs.distance(&'a', &'y'), None);
+        assert_eq!(
+            all_pairs.path(&'a', &'b'),
+            Some(vec!['a', 'e', 'd', 'c', 'b'])
+        );
+        assert_eq!(all_pairs.path(&'c', &'a'), Some(vec!['c', 'b', 'd', 'a']));
+        assert_eq!(all_pairs.path(&'b', &'b'), Some(vec!['b']));
+        assert_eq!(all_pairs.path(&'z', &'a'), None);
+        assert_eq!(all_pairs.distances_from(&'d').get(&'e'), Some(&-2));
+        assert_eq!(all_pairs.distances_from(&'z').len(), 1);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let mut graph = graph();
+        check_all_pairs(&graph.floyd_warshall().unwrap());
+
+        graph.add_edge('c', 'a', -10).unwrap();
+        let err = graph.floyd_warshall().unwrap_err();
+        assert_eq!(err.msg(), "negative cycle");
+        check_cycle(&graph, err.vertices());
+    }
+
+    fn check_cycle(graph: &Graph<char, i32>, cycle: &[char]) {
+        for i in 0..cycle.len() {
+            assert!(graph.contains_edge(&cycle[i], &cycle[(i + 1) % cycle.len()]));
+        }
+    }
+
+    #[test]
+    fn test_all_pairs_overflow() {
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert('a', 'b', -1_500_000_000);
+        graph.add_edge_or_insert('b', 'c', -1_500_000_000);
+        graph.add_edge_or_insert('c', 'a', -1_500_000_000);
+        let check = |err: GraphErr<char>| {
+            assert_eq!(err.msg(), "negative cycle");
+            assert_eq!(err.vertices(), &['a', 'b', 'c']);
+        };
+        check(graph.floyd_warshall().unwrap_err());
+        check(graph.johnson().unwrap_err());
+
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert('a', 'b', -1_500_000_000);
+        graph.add_edge_or_insert('b', 'c', -1_500_000_000);
+        let overflow = GraphErr::with_vertices("path weight overflows", vec!['a', 'c']);
+        assert_eq!(graph.floyd_warshall().unwrap_err(), overflow);
+        assert!(graph.johnson().is_err());
+
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert('a', 'b', i32::MAX);
+        graph.add_edge_or_insert('b', 'c', 1);
+        let overflow = GraphErr::with_vertices("path weight overflows", vec!['a', 'c']);
+        assert_eq!(graph.floyd_warshall().unwrap_err(), overflow);
+        assert_eq!(graph.johnson().unwrap_err(), overflow);
+        graph.add_edge_or_insert('a', 'c', 5);
+        assert_eq!(
+            graph.floyd_warshall().unwrap().distance(&'a', &'c'),
+            Some(5)
+        );
+        assert_eq!(graph.johnson().unwrap().distance(&'a', &'c'), Some(5));
+    }
+
+    #[test]
+    fn test_johnson() {
+        let mut graph = graph();
+        let johnson = graph.johnson().unwrap();
+        check_all_pairs(&johnson);
+        let floyd_warshall = graph.floyd_warshall().unwrap();
+        assert_eq!(johnson.matrix(), floyd_warshall.matrix());
+
+        graph.add_edge('c', 'a', -10).unwrap();
+        let err = graph.johnson().unwrap_err();
+        assert_eq!(err.msg(), "negative cycle");
+        check_cycle(&graph, err.vertices());
+    }
+
+    #[test]
+    fn test_undirected_all_pairs() {
+        let mut graph = Graph::new();
+        for v in 1..=4 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(1, 2, 1.0).unwrap();
+        graph.add_edge(2, 3, 2.5).unwrap();
+        graph.add_edge(1, 3, 4.0).unwrap();
+        graph.add_edge(3, 4, 0.5).unwrap();
+        let all_pairs = graph.johnson().unwrap();
+        assert_eq!(all_pairs.distance(&4, &1), Some(4.0));
+        assert_eq!(all_pairs.path(&4, &1), Some(vec![4, 3, 2, 1]));
+        let floyd_warshall = graph.floyd_warshall().unwrap();
+        assert_eq!(floyd_warshall.matrix(), all_pairs.matrix());
+        for v in 1..=4 {
+            assert_eq!(
+                all_pairs.distances_from(&v),
+                graph.dijkstra_all(v).unwrap().distances().clone()
+            );
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use super::{Graph, GraphErr, Weight};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+const PAGERANK_ITERATIONS: usize = 1000;
+
+#[derive(Debug)]
+pub struct Centrality<T> {
+    scores: HashMap<T, f64>,
+    ranked: Vec<(T, f64)>,
+}
+
+impl<T> Centrality<T>
+where
+    T: Eq + Hash + Clone,
+{
+    fn new(vertices: &[T], values: Vec<f64>) -> Self {
+        let mut ranked: Vec<(T, f64)> = vertices.iter().cloned().zip(values).collect();
+        let scores = ranked.iter().cloned().collect();
+        // stable sort, so ties keep insertion order
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        Centrality { scores, ranked }
+    }
+
+    pub fn scores(&self) -> &HashMap<T, f64> {
+        &self.scores
+    }
+
+    pub fn score<Q>(&self, vertex: &Q) -> Option<f64>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.scores.get(vertex).copied()
+    }
+
+    pub fn top_k(&self, k: usize) -> &[(T, f64)] {
+        &self.ranked[..k.min(self.ranked.len())]
+    }
+
+    pub fn into_scores(self) -> HashMap<T, f64> {
+        self.scores
+    }
+}
+
+// Centralities are computed on the unweighted structure: degree counts edges
+// and closeness and betweenness measure paths in hops.
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    // For directed graphs this is in-degree plus out-degree.
+    pub fn degree_centrality(&self) -> Centrality<T> {
+        let mut degrees = self.out_degrees();
+        if self.directed {
+            for (degree, in_degree) in degrees.iter_mut().zip(self.in_degrees()) {
+                *degree += in_degree;
+            }
+        }
+        self.normalized_degrees(degrees)
+    }
+
+    pub fn in_degree_centrality(&self) -> Centrality<T> {
+        self.normalized_degrees(self.in_degrees())
+    }
+
+    pub fn out_degree_centrality(&self) -> Centrality<T> {
+        self.normalized_degrees(self.out_degrees())
+    }
+
+    fn out_degrees(&self) -> Vec<usize> {
+        self.adj_list.iter().map(|edges| edges.len()).collect()
+    }
+
+    fn normalized_degrees(&self, degrees: Vec<usize>) -> Centrality<T> {
+        let others = self.vertex_count().saturating_sub(1).max(1) as f64;
+        let values = degrees.into_iter().map(|d| d as f64 / others).collect();
+        Centrality::new(&self.vertices, values)
+    }
+
+    // Uses outgoing distances and the Wasserman-Faust correction, so vertices
+    // that reach only part of the graph are scaled down instead of favoured.
+    pub fn closeness_centrality(&self) -> Centrality<T> {
+        let n = self.vertex_count();
+        let values = (0..n)
+            .map(|v| {
+                let (reached, total) = self
+                    .hops_from(v)
+                    .into_iter()
+                    .flatten()
+                    .fold((0, 0), |(reached, total), hops| (reached + 1, total + hops));
+                if total == 0 {
+                    return 0.0;
+                }
+                let reached = (reached - 1) as f64;
+                reached / total as f64 * reached / (n - 1) as f64
+            })
+            .collect();
+        Centrality::new(&self.vertices, values)
+    }
+
+    fn hops_from(&self, start: usize) -> Vec<Option<usize>> {
+        let mut hops = vec![None; self.vertex_count()];
+        hops[start] = Some(0);
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        while let Some(v) = queue.pop_front() {
+            for edge in &self.adj_list[v] {
+                if hops[edge.vertex].is_none() {
+                    hops[edge.vertex] = Some(hops[v].unwrap() + 1);
+                    queue.push_back(edge.vertex);
+                }
+            }
+        }
+        hops
+    }
+
+    // Brandes' algorithm, normalized by the number of pairs of other vertices.
+    pub fn betweenness_centrality(&self) -> Centrality<T> {
+        let n = self.vertex_count();
+        let mut betweenness = vec![0.0; n];
+        for s in 0..n {
+            let mut order = vec![];
+            let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+            let mut paths = vec![0.0; n];
+            let mut hops = vec![None; n];
+            paths[s] = 1.0;
+            hops[s] = Some(0);
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                let next = hops[v].unwrap() + 1;
+                for edge in &self.adj_list[v] {
+                    let w = edge.vertex;
+                    if hops[w].is_none() {
+                        hops[w] = Some(next);
+                        queue.push_back(w);
+                    }
+                    if hops[w] == Some(next) {
+                        paths[w] += paths[v];
+                        preds[w].push(v);
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0; n];
+            for w in order.into_iter().rev() {
+                for v in &preds[w] {
+                    dependency[*v] += paths[*v] / paths[w] * (1.0 + dependency[w]);
+                }
+                if w != s {
+                    betweenness[w] += dependency[w];
+                }
+            }
+        }
+        // Undirected graphs count every pair twice, which cancels out against
+        // having half as many unordered pairs.
+        if n > 2 {
+            let pairs = ((n - 1) * (n - 2)) as f64;
+            for value in betweenness.iter_mut() {
+                *value /= pairs;
+            }
+        }
+        Centrality::new(&self.vertices, betweenness)
+    }
+
+    // Rank that a vertex without outgoing edges would pass on is spread over
+    // all vertices. Iterates until the L1 change drops below `tolerance`.
+    pub fn pagerank(&self, damping: f64, tolerance: f64) -> Result<Centrality<T>, GraphErr<T>> {
+        if !(0.0..=1.0).contains(&damping) {
+            return Err(GraphErr::new("damping must be between 0 and 1"));
+        }
+        if tolerance.is_nan() || tolerance <= 0.0 {
+            return Err(GraphErr::new("tolerance must be positive"));
+        }
+        let n = self.vertex_count();
+        if n == 0 {
+            return Ok(Centrality::new(&[], vec![]));
+        }
+        let mut rank = vec![1.0 / n as f64; n];
+        for _ in 0..PAGERANK_ITERATIONS {
+            let dangling: f64 = (0..n)
+                .filter(|v| self.adj_list[*v].is_empty())
+                .map(|v| rank[v])
+                .sum();
+            let mut next = vec![(1.0 - damping + damping * dangling) / n as f64; n];
+            for (v, edges) in self.adj_list.iter().enumerate() {
+                for edge in edges {
+                    next[edge.vertex] += damping * rank[v] / edges.len() as f64;
+                }
+            }
+            let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if change < tolerance {
+                return Ok(Centrality::new(&self.vertices, rank));
+            }
+        }
+        Err(GraphErr::new("pagerank did not converge"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_scores(centrality: &Centrality<char>, expected: &[(char, f64)]) {
+        for (vertex, score) in expected {
+            let actual = centrality.score(vertex).unwrap();
+            assert!(
+                (actual - score).abs() < 1e-9,
+                "{}: expected {}, got {}",
+                vertex,
+                score,
+                actual
+            );
+        }
+    }
+
+    fn path(directed: bool) -> Graph<char> {
+        let mut graph = if directed {
+            Graph::new_directed()
+        } else {
+            Graph::new()
+        };
+        for (v1, v2) in "abcde".chars().zip("bcde".chars()) {
+            graph.add_edge_or_insert(v1, v2, 1);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_degree_and_closeness() {
+        let graph = path(false);
+        assert_scores(
+            &graph.degree_centrality(),
+            &[('a', 0.25), ('b', 0.5), ('c', 0.5)],
+        );
+        assert_scores(
+            &graph.closeness_centrality(),
+            &[('a', 0.4), ('b', 4.0 / 7.0), ('c', 4.0 / 6.0)],
+        );
+
+        let graph = path(true);
+        assert_scores(&graph.degree_centrality(), &[('a', 0.25), ('c', 0.5)]);
+        assert_scores(&graph.in_degree_centrality(), &[('a', 0.0), ('e', 0.25)]);
+        assert_scores(&graph.out_degree_centrality(), &[('a', 0.25), ('e', 0.0)]);
+        // 'd' reaches only 'e', so it is scaled by the 1 of 4 vertices it reaches
+        assert_scores(
+            &graph.closeness_centrality(),
+            &[('a', 0.4), ('d', 0.25), ('e', 0.0)],
+        );
+        assert!(Graph::<char>::new()
+            .closeness_centrality()
+            .scores()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_betweenness() {
+        assert_scores(
+            &path(false).betweenness_centrality(),
+            &[('a', 0.0), ('b', 0.5), ('c', 4.0 / 6.0), ('d', 0.5)],
+        );
+        assert_scores(
+            &path(true).betweenness_centrality(),
+            &[('a', 0.0), ('b', 0.25), ('c', 4.0 / 12.0)],
+        );
+
+        // two shortest paths between 'a' and 'd', each carrying half
+        let mut diamond = Graph::new();
+        diamond.add_edge_or_insert('a', 'b', 1);
+        diamond.add_edge_or_insert('a', 'c', 1);
+        diamond.add_edge_or_insert('b', 'd', 1);
+        diamond.add_edge_or_insert('c', 'd', 1);
+        let centrality = diamond.betweenness_centrality();
+        assert_scores(&centrality, &[('a', 1.0 / 6.0), ('b', 1.0 / 6.0)]);
+        assert_eq!(centrality.top_k(10).len(), 4);
+        assert_eq!(centrality.top_k(1), &[('a', 1.0 / 6.0)]);
+    }
+
+    #[test]
+    fn test_pagerank() {
+        let mut graph = Graph::new_directed();
+        for (v1, v2) in &[("a", "b"), ("b", "c"), ("c", "a")] {
+            graph.add_edge_or_insert(*v1, *v2, 1);
+        }
+        let ranks = graph.pagerank(0.85, 1e-10).unwrap();
+        for rank in ranks.scores().values() {
+            assert!((rank - 1.0 / 3.0).abs() < 1e-9);
+        }
+
+        // a citation graph: most papers cite "survey", which cites "origin"
+        graph.add_edge_or_insert("d", "survey", 1);
+        graph.add_edge_or_insert("e", "survey", 1);
+        graph.add_edge_or_insert("a", "survey", 1);
+        graph.add_edge_or_insert("survey", "origin", 1);
+        let ranks = graph.pagerank(0.85, 1e-10).unwrap();
+        let total: f64 = ranks.scores().values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let top: Vec<_> = ranks.top_k(2).iter().map(|(v, _)| *v).collect();
+        assert_eq!(top, vec!["origin", "survey"]);
+        assert_eq!(ranks.score("d"), ranks.score("e"));
+        assert_eq!(ranks.score("z"), None);
+
+        // without damping every vertex is equally likely
+        let ranks = graph.pagerank(0.0, 1e-10).unwrap();
+        assert!((ranks.score("origin").unwrap() - 1.0 / 7.0).abs() < 1e-12);
+        assert!(graph.pagerank(1.5, 1e-6).is_err());
+        assert!(graph.pagerank(0.85, 0.0).is_err());
+    }
+}
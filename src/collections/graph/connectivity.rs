@@ -0,0 +1,409 @@
+use super::traversal::Traversal;
+use super::{Edge, Graph, GraphErr, Weight};
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
+struct Biconnected {
+    articulation_points: BTreeSet<usize>,
+    bridges: Vec<(usize, usize)>,
+    components: Vec<Vec<usize>>,
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn connected_components(&self) -> Result<Vec<Vec<T>>, GraphErr<T>> {
+        self.check_undirected()?;
+        let order: Vec<usize> = (0..self.vertex_count()).collect();
+        Ok(self
+            .forest(&order)
+            .iter()
+            .map(|component| self.vertices_of(component))
+            .collect())
+    }
+
+    pub fn strongly_connected_components(&self) -> Vec<Vec<T>> {
+        let mut visited = vec![false; self.vertex_count()];
+        let mut finished = vec![];
+        for v in 0..self.vertex_count() {
+            if !visited[v] {
+                self.dfs_post_order(v, &mut visited, &mut finished);
+            }
+        }
+
+        finished.reverse();
+        let mut components = self.transpose().forest(&finished);
+        components.sort();
+        components
+            .iter()
+            .map(|component| self.vertices_of(component))
+            .collect()
+    }
+
+    // One tree per start in `order` that no earlier start reached, each
+    // sorted by id.
+    fn forest(&self, order: &[usize]) -> Vec<Vec<usize>> {
+        let mut traversal = Traversal::from_ids(self, order);
+        let mut trees: Vec<Vec<usize>> = vec![];
+        while let Some(visit) = traversal.next_id() {
+            match trees.last_mut() {
+                Some(tree) if visit.depth() > 0 => tree.push(visit.vertex()),
+                _ => trees.push(vec![visit.vertex()]),
+            }
+        }
+        for tree in trees.iter_mut() {
+            tree.sort_unstable();
+        }
+        trees
+    }
+
+    // `reverse` without edge data, so no `E: Clone` is needed.
+    fn transpose(&self) -> Graph<T, W> {
+        let mut adj_list = vec![vec![]; self.vertex_count()];
+        for (v, edges) in self.adj_list.iter().enumerate() {
+            for edge in edges {
+                adj_list[edge.vertex].push(Edge {
+                    vertex: v,
+                    weight: edge.weight,
+                    data: (),
+                });
+            }
+        }
+        Graph {
+            vertices: self.vertices.clone(),
+            index: self.index.clone(),
+            adj_list,
+            directed: self.directed,
+        }
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    pub fn find_cycle(&self) -> Option<Vec<T>> {
+        // `false` marks vertices still on the DFS stack, `true` finished ones.
+        let mut state: Vec<Option<bool>> = vec![None; self.vertex_count()];
+        for root in 0..self.vertex_count() {
+            if state[root].is_some() {
+                continue;
+            }
+            let mut stack: Vec<(usize, usize, Option<usize>)> = vec![(root, 0, None)];
+            state[root] = Some(false);
+            while let Some((v, idx, parent)) = stack.last().copied() {
+                let edges = &self.adj_list[v];
+                if idx == edges.len() {
+                    state[v] = Some(true);
+                    stack.pop();
+                    continue;
+                }
+                let top = stack.len() - 1;
+                stack[top].1 += 1;
+                let next_vertex = edges[idx].vertex;
+                if !self.directed && Some(next_vertex) == parent {
+                    // Only the edge we arrived through is ignored, so parallel
+                    // undirected edges still count as a cycle.
+                    stack[top].2 = None;
+                    continue;
+                }
+                match state[next_vertex] {
+                    None => {
+                        state[next_vertex] = Some(false);
+                        stack.push((next_vertex, 0, Some(v)));
+                    }
+                    Some(false) => {
+                        let pos = stack.iter().position(|f| f.0 == next_vertex).unwrap();
+                        let cycle: Vec<usize> = stack[pos..].iter().map(|f| f.0).collect();
+                        return Some(self.vertices_of(&cycle));
+                    }
+                    Some(true) => {}
+                }
+            }
+        }
+        None
+    }
+
+    pub fn articulation_points(&self) -> Result<Vec<T>, GraphErr<T>> {
+        let points: Vec<usize> = self
+            .biconnected()?
+            .articulation_points
+            .into_iter()
+            .collect();
+        Ok(self.vertices_of(&points))
+    }
+
+    pub fn bridges(&self) -> Result<Vec<(T, T)>, GraphErr<T>> {
+        Ok(self
+            .biconnected()?
+            .bridges
+            .into_iter()
+            .map(|(u, v)| (self.vertices[u].clone(), self.vertices[v].clone()))
+            .collect())
+    }
+
+    pub fn biconnected_components(&self) -> Result<Vec<Vec<T>>, GraphErr<T>> {
+        Ok(self
+            .biconnected()?
+            .components
+            .iter()
+            .map(|component| self.vertices_of(component))
+            .collect())
+    }
+
+    // Tarjan's low-link DFS, kept iterative with an explicit frame stack.
+    fn biconnected(&self) -> Result<Biconnected, GraphErr<T>> {
+        self.check_undirected()?;
+        let mut result = Biconnected {
+            articulation_points: BTreeSet::new(),
+            bridges: vec![],
+            components: vec![],
+        };
+        let n = self.vertex_count();
+        let mut discovered: Vec<Option<usize>> = vec![None; n];
+        let mut low = vec![0; n];
+        let mut time = 0;
+        for root in 0..n {
+            if discovered[root].is_some() {
+                continue;
+            }
+            discovered[root] = Some(time);
+            low[root] = time;
+            time += 1;
+            if self.adj_list[root].iter().all(|edge| edge.vertex == root) {
+                result.components.push(vec![root]);
+                continue;
+            }
+            let mut root_children = 0;
+            let mut edges: Vec<(usize, usize)> = vec![];
+            let mut stack: Vec<(usize, usize, Option<usize>)> = vec![(root, 0, None)];
+            while let Some((v, idx, parent)) = stack.last().copied() {
+                let adjacent = &self.adj_list[v];
+                if idx == adjacent.len() {
+                    stack.pop();
+                    let u = match stack.last() {
+                        Some(frame) => frame.0,
+                        None => continue,
+                    };
+                    let discovered_u = discovered[u].unwrap_or(0);
+                    low[u] = low[u].min(low[v]);
+                    if low[v] >= discovered_u {
+                        if u != root {
+                            result.articulation_points.insert(u);
+                        }
+                        let mut component = BTreeSet::new();
+                        while let Some((a, b)) = edges.pop() {
+                            component.insert(a);
+                            component.insert(b);
+                            if (a, b) == (u, v) {
+                                break;
+                            }
+                        }
+                        result.components.push(component.into_iter().collect());
+                    }
+                    if low[v] > discovered_u {
+                        result.bridges.push((u.min(v), u.max(v)));
+                    }
+                    continue;
+                }
+                let top = stack.len() - 1;
+                stack[top].1 += 1;
+                let w = adjacent[idx].vertex;
+                if w == v {
+                    continue;
+                }
+                if Some(w) == parent {
+                    // Skip the tree edge once; a parallel copy is a back edge.
+                    stack[top].2 = None;
+                    continue;
+                }
+                match discovered[w] {
+                    None => {
+                        discovered[w] = Some(time);
+                        low[w] = time;
+                        time += 1;
+                        edges.push((v, w));
+                        stack.push((w, 0, Some(v)));
+                        if v == root {
+                            root_children += 1;
+                        }
+                    }
+                    Some(order) if Some(order) < discovered[v] => {
+                        low[v] = low[v].min(order);
+                        edges.push((v, w));
+                    }
+                    Some(_) => {}
+                }
+            }
+            if root_children > 1 {
+                result.articulation_points.insert(root);
+            }
+        }
+        result.bridges.sort_unstable();
+        result.components.sort();
+        Ok(result)
+    }
+
+    fn dfs_post_order(&self, start: usize, visited: &mut [bool], finished: &mut Vec<usize>) {
+        let mut stack = vec![(start, 0)];
+        visited[start] = true;
+        while let Some((v, idx)) = stack.last().copied() {
+            let edges = &self.adj_list[v];
+            if idx == edges.len() {
+                finished.push(v);
+                stack.pop();
+                continue;
+            }
+            let top = stack.len() - 1;
+            stack[top].1 += 1;
+            let next_vertex = edges[idx].vertex;
+            if !visited[next_vertex] {
+                visited[next_vertex] = true;
+                stack.push((next_vertex, 0));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut graph = Graph::new_directed();
+        for v in "abcdefgh".chars() {
+            graph.add_vertex(v);
+        }
+        graph.add_edge('a', 'b', 1).unwrap();
+        graph.add_edge('b', 'c', 1).unwrap();
+        graph.add_edge('c', 'a', 1).unwrap();
+        graph.add_edge('b', 'd', 1).unwrap();
+        graph.add_edge('d', 'e', 1).unwrap();
+        graph.add_edge('e', 'f', 1).unwrap();
+        graph.add_edge('f', 'd', 1).unwrap();
+        graph.add_edge('g', 'f', 1).unwrap();
+        graph.add_edge('g', 'h', 1).unwrap();
+        graph.add_edge('h', 'g', 1).unwrap();
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g', 'h']]
+        );
+        assert!(graph.connected_components().is_err());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph = Graph::new_directed();
+        for v in 1..=5 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(1, 2, 1).unwrap();
+        graph.add_edge(1, 3, 1).unwrap();
+        graph.add_edge(2, 4, 1).unwrap();
+        graph.add_edge(3, 4, 1).unwrap();
+        graph.add_edge(4, 5, 1).unwrap();
+        assert!(!graph.has_cycle());
+        assert_eq!(graph.find_cycle(), None);
+        assert_eq!(graph.strongly_connected_components().len(), 5);
+
+        graph.add_edge(5, 3, 1).unwrap();
+        assert!(graph.has_cycle());
+        assert_eq!(graph.find_cycle(), Some(vec![4, 5, 3]));
+
+        let mut graph = Graph::new();
+        for v in 1..=4 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(1, 2, 1).unwrap();
+        graph.add_edge(2, 3, 1).unwrap();
+        graph.add_edge(2, 4, 1).unwrap();
+        assert_eq!(graph.find_cycle(), None);
+        graph.add_edge(4, 1, 1).unwrap();
+        assert_eq!(graph.find_cycle(), Some(vec![1, 2, 4]));
+
+        let mut graph = Graph::new();
+        graph.add_vertex("x");
+        graph.add_vertex("y");
+        graph.add_edge("x", "y", 1).unwrap();
+        assert!(!graph.has_cycle());
+        graph.add_edge("y", "x", 2).unwrap();
+        assert_eq!(graph.find_cycle(), Some(vec!["x", "y"]));
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph = Graph::new();
+        for v in 0..8 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(5, 0, 1).unwrap();
+        graph.add_edge(0, 3, 1).unwrap();
+        graph.add_edge(1, 6, 1).unwrap();
+        graph.add_edge(6, 4, 1).unwrap();
+        assert_eq!(
+            graph.connected_components().unwrap(),
+            vec![vec![0, 3, 5], vec![1, 4, 6], vec![2], vec![7]]
+        );
+        assert_eq!(
+            graph.strongly_connected_components(),
+            graph.connected_components().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_biconnected() {
+        // two triangles joined at 3, with a tail 5-6-7 hanging off 4
+        let mut graph = Graph::new();
+        for v in 0..9 {
+            graph.add_vertex(v);
+        }
+        for (a, b) in &[
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 2),
+            (4, 5),
+            (5, 6),
+            (6, 7),
+        ] {
+            graph.add_edge(*a, *b, 1).unwrap();
+        }
+        assert_eq!(graph.articulation_points(), Ok(vec![2, 4, 5, 6]));
+        assert_eq!(graph.bridges(), Ok(vec![(4, 5), (5, 6), (6, 7)]));
+        assert_eq!(
+            graph.biconnected_components(),
+            Ok(vec![
+                vec![0, 1, 2],
+                vec![2, 3, 4],
+                vec![4, 5],
+                vec![5, 6],
+                vec![6, 7],
+                vec![8]
+            ])
+        );
+
+        graph.add_edge(6, 7, 2).unwrap();
+        graph.add_edge(8, 8, 1).unwrap();
+        assert_eq!(graph.bridges(), Ok(vec![(4, 5), (5, 6)]));
+        assert_eq!(graph.articulation_points(), Ok(vec![2, 4, 5, 6]));
+        assert!(Graph::<i32>::new_directed().bridges().is_err());
+    }
+
+    #[test]
+    fn test_biconnected_long_path() {
+        let mut graph = Graph::new();
+        graph.add_vertex(0);
+        for v in 1..100_000 {
+            graph.add_edge_or_insert(v - 1, v, 1);
+        }
+        assert_eq!(graph.bridges().unwrap().len(), 99_999);
+        assert_eq!(graph.articulation_points().unwrap().len(), 99_998);
+        graph.add_edge(99_999, 0, 1).unwrap();
+        assert_eq!(graph.bridges(), Ok(vec![]));
+        assert_eq!(graph.biconnected_components().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+use super::shortest_path::{dijkstra_tree, path_ids};
+use super::{Graph, GraphErr, Weight};
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct CsrGraph<T, W = usize> {
+    pub(super) vertices: Vec<T>,
+    pub(super) index: HashMap<T, usize>,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+    directed: bool,
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn freeze(&self) -> CsrGraph<T, W> {
+        let mut offsets = Vec::with_capacity(self.vertex_count() + 1);
+        let mut targets = vec![];
+        let mut weights = vec![];
+        offsets.push(0);
+        for edges in &self.adj_list {
+            for edge in edges {
+                targets.push(edge.vertex);
+                weights.push(edge.weight);
+            }
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            vertices: self.vertices.clone(),
+            index: self.index.clone(),
+            offsets,
+            targets,
+            weights,
+            directed: self.directed,
+        }
+    }
+}
+
+impl<T, W> CsrGraph<T, W>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        if self.directed {
+            self.targets.len()
+        } else {
+            self.targets.len() / 2
+        }
+    }
+
+    pub fn contains_vertex<Q>(&self, vertex: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.index.contains_key(vertex)
+    }
+
+    pub fn neighbors<Q>(&self, vertex: &Q) -> Vec<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.index.get(vertex) {
+            Some(idx) => self.targets[self.edge_range(*idx)]
+                .iter()
+                .map(|target| self.vertices[*target].clone())
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn dfs_iter(&self, start: T) -> Vec<T> {
+        let mut data = vec![];
+        if let Some(start) = self.index.get(&start) {
+            // same order as `Graph::dfs_iter`: mark on pop, last neighbor first
+            let mut visited = vec![false; self.vertices.len()];
+            let mut stack = vec![*start];
+            while let Some(v) = stack.pop() {
+                if visited[v] {
+                    continue;
+                }
+                visited[v] = true;
+                data.push(self.vertices[v].clone());
+                for target in &self.targets[self.edge_range(v)] {
+                    if !visited[*target] {
+                        stack.push(*target);
+                    }
+                }
+            }
+        }
+        data
+    }
+
+    pub fn bfs(&self, start: T) -> Vec<T> {
+        let mut data = vec![];
+        if let Some(start) = self.index.get(&start) {
+            let mut visited = vec![false; self.vertices.len()];
+            let mut queue = VecDeque::new();
+            queue.push_back(*start);
+            visited[*start] = true;
+            while let Some(v) = queue.pop_front() {
+                data.push(self.vertices[v].clone());
+                for target in &self.targets[self.edge_range(v)] {
+                    if !visited[*target] {
+                        visited[*target] = true;
+                        queue.push_back(*target);
+                    }
+                }
+            }
+        }
+        data
+    }
+
+    pub fn dijkstra(&self, start: T, end: T) -> Result<Option<(W, Vec<T>)>, GraphErr<T>> {
+        let start = self.vertex_index(start)?;
+        let end = self.vertex_index(end)?;
+        let (distances, prev) = dijkstra_tree(&self.vertices, start, Some(end), |v| self.edges(v))?;
+        Ok(distances[end].map(|dist| (dist, path_ids(&self.vertices, &prev, end))))
+    }
+
+    fn vertex_index(&self, vertex: T) -> Result<usize, GraphErr<T>> {
+        match self.index.get(&vertex) {
+            Some(idx) => Ok(*idx),
+            None => Err(GraphErr::with_vertices("vertex not found", vec![vertex])),
+        }
+    }
+
+    pub(super) fn vertices_of(&self, ids: &[usize]) -> Vec<T> {
+        ids.iter().map(|id| self.vertices[*id].clone()).collect()
+    }
+
+    pub(super) fn edges(&self, idx: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.edge_range(idx)
+            .map(move |edge| (self.targets[edge], self.weights[edge]))
+    }
+
+    fn edge_range(&self, idx: usize) -> std::ops::Range<usize> {
+        self.offsets[idx]..self.offsets[idx + 1]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::Edge;
+    use super::*;
+    use std::mem::{size_of, size_of_val};
+
+    fn grid(size: i32) -> Graph<i32> {
+        let mut graph = Graph::new();
+        for v in 0..size * size {
+            graph.add_vertex(v);
+        }
+        for v in 0..size * size {
+            if v % size + 1 < size {
+                graph.add_edge(v, v + 1, (v % 7 + 1) as usize).unwrap();
+            }
+            if v / size + 1 < size {
+                graph.add_edge(v, v + size, (v % 5 + 1) as usize).unwrap();
+            }
+        }
+        graph
+    }
+
+    fn assert_send_sync<X: Send + Sync>(_: &X) {}
+
+    #[test]
+    fn test_csr_graph() {
+        let graph = grid(12);
+        let csr = graph.freeze();
+        assert_send_sync(&csr);
+        assert!(!csr.is_directed());
+        assert_eq!(csr.vertex_count(), graph.vertex_count());
+        assert_eq!(csr.edge_count(), graph.edge_count());
+        assert_eq!(csr.neighbors(&13), graph.neighbors(&13));
+        assert!(csr.contains_vertex(&0));
+        assert!(!csr.contains_vertex(&-1));
+        for start in &[0, 17, 143] {
+            assert_eq!(csr.bfs(*start), graph.bfs(*start));
+            let dfs: Vec<_> = graph
+                .dfs_iter(*start)
+                .map(|visit| *visit.vertex())
+                .collect();
+            assert_eq!(csr.dfs_iter(*start), dfs);
+            for end in &[0, 5, 77, 143] {
+                assert_eq!(csr.dijkstra(*start, *end), graph.dijkstra(*start, *end));
+            }
+        }
+        assert_eq!(csr.bfs(-1), vec![]);
+        assert!(csr.dijkstra(0, -1).is_err());
+
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert("a", "b", 2.5);
+        graph.add_edge_or_insert("b", "c", 1.0);
+        graph.add_edge_or_insert("a", "c", 4.0);
+        graph.add_vertex("d");
+        let csr = graph.freeze();
+        assert_eq!(csr.edge_count(), 3);
+        assert_eq!(csr.dijkstra("a", "c"), Ok(Some((3.5, vec!["a", "b", "c"]))));
+        assert_eq!(csr.dijkstra("c", "a"), Ok(None));
+        assert_eq!(csr.dfs_iter("d"), vec!["d"]);
+
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert(1, 2, usize::MAX);
+        graph.add_edge_or_insert(2, 3, 1);
+        let csr = graph.freeze();
+        assert_eq!(csr.dijkstra(1, 2), Ok(Some((usize::MAX, vec![1, 2]))));
+        assert_eq!(csr.dijkstra(1, 3), graph.dijkstra(1, 3));
+        assert_eq!(csr.dijkstra(1, 3).unwrap_err().vertices(), &[3]);
+
+        // preorder, as opposed to marking vertices when they are pushed
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert(0, 1, 1);
+        graph.add_edge_or_insert(0, 2, 1);
+        graph.add_edge_or_insert(2, 3, 1);
+        graph.add_edge_or_insert(2, 1, 1);
+        let csr = graph.freeze();
+        assert_eq!(csr.dfs_iter(0), vec![0, 2, 1, 3]);
+        let dfs: Vec<_> = graph.dfs_iter(0).map(|visit| *visit.vertex()).collect();
+        assert_eq!(csr.dfs_iter(0), dfs);
+        assert_eq!(graph.dfs_recur(0), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_csr_edge_storage() {
+        let graph = grid(300);
+        let csr = graph.freeze();
+        // one `Vec<Edge>` per vertex against the three flat CSR arrays
+        let graph_bytes: usize = graph
+            .adj_list
+            .iter()
+            .map(|edges| {
+                size_of::<Vec<Edge<usize, ()>>>() + edges.len() * size_of::<Edge<usize, ()>>()
+            })
+            .sum();
+        let csr_bytes = size_of_val(&csr.offsets[..])
+            + size_of_val(&csr.targets[..])
+            + size_of_val(&csr.weights[..]);
+        assert!(csr_bytes < graph_bytes);
+    }
+}
@@ -0,0 +1,242 @@
+use super::{Graph, GraphErr, Weight};
+use std::hash::Hash;
+
+pub type Walk<T, W> = Vec<(T, T, W)>;
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    // Walk that uses every edge exactly once and ends where it started.
+    pub fn eulerian_circuit(&self) -> Result<Walk<T, W>, GraphErr<T>> {
+        let start = if self.directed {
+            let unbalanced = self.unbalanced_vertices();
+            if !unbalanced.is_empty() {
+                return Err(GraphErr::with_vertices(
+                    "in-degree and out-degree differ",
+                    self.vertices_of(&unbalanced),
+                ));
+            }
+            self.first_with_edges()
+        } else {
+            let odd = self.odd_vertices();
+            if !odd.is_empty() {
+                return Err(GraphErr::with_vertices(
+                    "vertices have odd degree",
+                    self.vertices_of(&odd),
+                ));
+            }
+            self.first_with_edges()
+        };
+        self.hierholzer(start)
+    }
+
+    // Walk that uses every edge exactly once; it is a circuit whenever the
+    // degrees allow one.
+    pub fn eulerian_path(&self) -> Result<Walk<T, W>, GraphErr<T>> {
+        let start = if self.directed {
+            let unbalanced = self.unbalanced_vertices();
+            let in_degrees = self.in_degrees();
+            let surplus = |v: usize| self.adj_list[v].len() as isize - in_degrees[v] as isize;
+            let starts: Vec<usize> = unbalanced
+                .iter()
+                .cloned()
+                .filter(|v| surplus(*v) == 1)
+                .collect();
+            let ends = unbalanced.iter().filter(|v| surplus(**v) == -1).count();
+            if unbalanced.len() > 2 || starts.len() != ends || starts.len() != unbalanced.len() / 2
+            {
+                return Err(GraphErr::with_vertices(
+                    "in-degree and out-degree differ by more than one start and one end",
+                    self.vertices_of(&unbalanced),
+                ));
+            }
+            starts.first().cloned().or_else(|| self.first_with_edges())
+        } else {
+            let odd = self.odd_vertices();
+            if odd.len() > 2 {
+                return Err(GraphErr::with_vertices(
+                    "more than two vertices have odd degree",
+                    self.vertices_of(&odd),
+                ));
+            }
+            odd.first().cloned().or_else(|| self.first_with_edges())
+        };
+        self.hierholzer(start)
+    }
+
+    fn odd_vertices(&self) -> Vec<usize> {
+        (0..self.vertex_count())
+            .filter(|v| self.adj_list[*v].len() % 2 == 1)
+            .collect()
+    }
+
+    fn unbalanced_vertices(&self) -> Vec<usize> {
+        let in_degrees = self.in_degrees();
+        (0..self.vertex_count())
+            .filter(|v| self.adj_list[*v].len() != in_degrees[*v])
+            .collect()
+    }
+
+    fn first_with_edges(&self) -> Option<usize> {
+        (0..self.vertex_count()).find(|v| !self.adj_list[*v].is_empty())
+    }
+
+    fn hierholzer(&self, start: Option<usize>) -> Result<Walk<T, W>, GraphErr<T>> {
+        let start = match start {
+            Some(start) => start,
+            None => return Ok(vec![]),
+        };
+        let edges = self.unique_edges();
+        // (neighbor, edge index) pairs, so both directions of an undirected
+        // edge are used up together
+        let mut incident = vec![vec![]; self.vertex_count()];
+        for (i, (v1, v2, _)) in edges.iter().enumerate() {
+            incident[*v1].push((*v2, i));
+            if !self.directed && v1 != v2 {
+                incident[*v2].push((*v1, i));
+            }
+        }
+
+        let mut used = vec![false; edges.len()];
+        let mut next = vec![0; self.vertex_count()];
+        let mut stack: Vec<(usize, Option<(usize, usize)>)> = vec![(start, None)];
+        let mut walk = vec![];
+        while let Some(&(v, via)) = stack.last() {
+            while next[v] < incident[v].len() && used[incident[v][next[v]].1] {
+                next[v] += 1;
+            }
+            match incident[v].get(next[v]) {
+                Some(&(w, i)) => {
+                    used[i] = true;
+                    stack.push((w, Some((v, i))));
+                }
+                None => {
+                    stack.pop();
+                    if let Some((from, i)) = via {
+                        walk.push((
+                            self.vertices[from].clone(),
+                            self.vertices[v].clone(),
+                            edges[i].2,
+                        ));
+                    }
+                }
+            }
+        }
+
+        if walk.len() < edges.len() {
+            let stranded: Vec<usize> = edges
+                .iter()
+                .zip(&used)
+                .filter(|(_, used)| !**used)
+                .map(|((v1, _, _), _)| *v1)
+                .collect();
+            return Err(GraphErr::with_vertices(
+                "edges are not connected",
+                self.vertices_of(&stranded[..1]),
+            ));
+        }
+        walk.reverse();
+        Ok(walk)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph(directed: bool, edges: &[(char, char)]) -> Graph<char> {
+        let mut graph = if directed {
+            Graph::new_directed()
+        } else {
+            Graph::new()
+        };
+        for (v1, v2) in edges {
+            graph.add_edge_or_insert(*v1, *v2, 1);
+        }
+        graph
+    }
+
+    // Checks that the walk is connected and uses each edge exactly once.
+    fn assert_walk(walk: &[(char, char, usize)], edges: &[(char, char)], directed: bool) {
+        for pair in walk.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+        let normalize = |v1: char, v2: char| {
+            if directed || v1 < v2 {
+                (v1, v2)
+            } else {
+                (v2, v1)
+            }
+        };
+        let mut walked: Vec<_> = walk.iter().map(|(v1, v2, _)| normalize(*v1, *v2)).collect();
+        let mut expected: Vec<_> = edges.iter().map(|(v1, v2)| normalize(*v1, *v2)).collect();
+        walked.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(walked, expected);
+    }
+
+    #[test]
+    fn test_eulerian_undirected() {
+        // Königsberg with one extra bridge: only 'a' and 'd' have odd degree
+        let mut edges = vec![
+            ('a', 'b'),
+            ('a', 'b'),
+            ('a', 'c'),
+            ('a', 'c'),
+            ('a', 'd'),
+            ('b', 'd'),
+            ('c', 'd'),
+            ('b', 'c'),
+        ];
+        let err = graph(false, &edges).eulerian_circuit().unwrap_err();
+        assert_eq!(err.msg(), "vertices have odd degree");
+        assert_eq!(err.vertices(), &['a', 'd']);
+        let walk = graph(false, &edges).eulerian_path().unwrap();
+        assert_eq!((walk[0].0, walk[walk.len() - 1].1), ('a', 'd'));
+        assert_walk(&walk, &edges, false);
+
+        edges.push(('d', 'a'));
+        edges.push(('c', 'c'));
+        let walk = graph(false, &edges).eulerian_circuit().unwrap();
+        assert_eq!(walk, graph(false, &edges).eulerian_path().unwrap());
+        assert_eq!(walk[0].0, walk[walk.len() - 1].1);
+        assert_walk(&walk, &edges, false);
+
+        edges.push(('b', 'e'));
+        edges.push(('x', 'y'));
+        let err = graph(false, &edges).eulerian_path().unwrap_err();
+        assert_eq!(err.vertices(), &['b', 'e', 'x', 'y']);
+        let err = graph(false, &[('a', 'b'), ('b', 'a'), ('x', 'y')])
+            .eulerian_path()
+            .unwrap_err();
+        assert_eq!(err.msg(), "edges are not connected");
+        assert_eq!(Graph::<char>::new().eulerian_circuit().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_eulerian_directed() {
+        let mut edges = vec![
+            ('a', 'b'),
+            ('b', 'c'),
+            ('c', 'a'),
+            ('c', 'd'),
+            ('d', 'c'),
+            ('c', 'c'),
+        ];
+        let walk = graph(true, &edges).eulerian_circuit().unwrap();
+        assert_eq!((walk[0].0, walk[walk.len() - 1].1), ('a', 'a'));
+        assert_walk(&walk, &edges, true);
+
+        edges.push(('a', 'd'));
+        let err = graph(true, &edges).eulerian_circuit().unwrap_err();
+        assert_eq!(err.vertices(), &['a', 'd']);
+        let walk = graph(true, &edges).eulerian_path().unwrap();
+        assert_eq!((walk[0].0, walk[walk.len() - 1].1), ('a', 'd'));
+        assert_walk(&walk, &edges, true);
+
+        edges.push(('a', 'c'));
+        assert!(graph(true, &edges).eulerian_path().is_err());
+    }
+}
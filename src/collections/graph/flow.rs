@@ -0,0 +1,375 @@
+use super::{checked_add, Graph, GraphErr, Weight};
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+#[derive(Debug)]
+pub struct MaxFlow<T, W = usize> {
+    value: W,
+    flows: Vec<(T, T, W)>,
+    cut_edges: Vec<(T, T, W)>,
+    source_side: Vec<T>,
+    sink_side: Vec<T>,
+}
+
+impl<T, W: Weight> MaxFlow<T, W> {
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    pub fn flows(&self) -> &[(T, T, W)] {
+        &self.flows
+    }
+
+    pub fn cut_edges(&self) -> &[(T, T, W)] {
+        &self.cut_edges
+    }
+
+    pub fn min_cut(&self) -> (&[T], &[T]) {
+        (&self.source_side, &self.sink_side)
+    }
+}
+
+struct Residual<W> {
+    head: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<W>,
+    // (from, to, capacity, arc) for every edge of the original graph; an
+    // undirected edge is a pair of opposite arcs, the second at `arc + 2`,
+    // so no residual ever exceeds the capacity
+    edges: Vec<(usize, usize, W, usize)>,
+    directed: bool,
+}
+
+impl<W: Weight> Residual<W> {
+    fn add_arc(&mut self, u: usize, v: usize, cap: W) -> usize {
+        let arc = self.to.len();
+        self.head[u].push(arc);
+        self.to.push(v);
+        self.cap.push(cap);
+        self.head[v].push(arc + 1);
+        self.to.push(u);
+        self.cap.push(W::zero());
+        arc
+    }
+
+    fn augment(&mut self, path: &[usize]) -> W {
+        let mut bottleneck = self.cap[path[0]];
+        for arc in path {
+            if self.cap[*arc] < bottleneck {
+                bottleneck = self.cap[*arc];
+            }
+        }
+        for arc in path {
+            self.cap[*arc] = self.cap[*arc] - bottleneck;
+            self.cap[*arc ^ 1] = self.cap[*arc ^ 1] + bottleneck;
+        }
+        bottleneck
+    }
+
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.head.len()];
+        let mut queue = VecDeque::new();
+        levels[source] = Some(0);
+        queue.push_back(source);
+        while let Some(u) = queue.pop_front() {
+            for arc in &self.head[u] {
+                let v = self.to[*arc];
+                if levels[v].is_none() && self.cap[*arc] > W::zero() {
+                    levels[v] = levels[u].map(|level| level + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+        levels
+    }
+
+    fn into_max_flow<T: Clone>(self, vertices: &[T], source: usize, value: W) -> MaxFlow<T, W> {
+        let levels = self.levels(source);
+        let mut flows = vec![];
+        let mut cut_edges = vec![];
+        for (u, v, cap, arc) in &self.edges {
+            let forward = *cap - self.cap[*arc];
+            let backward = if self.directed {
+                W::zero()
+            } else {
+                *cap - self.cap[*arc + 2]
+            };
+            let (from, to) = (vertices[*u].clone(), vertices[*v].clone());
+            if levels[*u].is_some() && levels[*v].is_none() {
+                cut_edges.push((from.clone(), to.clone(), *cap));
+            } else if !self.directed && levels[*v].is_some() && levels[*u].is_none() {
+                cut_edges.push((to.clone(), from.clone(), *cap));
+            }
+            if backward > forward {
+                flows.push((to, from, backward - forward));
+            } else {
+                flows.push((from, to, forward - backward));
+            }
+        }
+        let mut source_side = vec![];
+        let mut sink_side = vec![];
+        for (idx, v) in vertices.iter().enumerate() {
+            if levels[idx].is_some() {
+                source_side.push(v.clone());
+            } else {
+                sink_side.push(v.clone());
+            }
+        }
+        MaxFlow {
+            value,
+            flows,
+            cut_edges,
+            source_side,
+            sink_side,
+        }
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn edmonds_karp(&self, source: T, sink: T) -> Result<MaxFlow<T, W>, GraphErr<T>> {
+        let (mut residual, s, t) = self.residual(source, sink)?;
+        let mut value = W::zero();
+        loop {
+            let mut prev_arc: Vec<Option<usize>> = vec![None; residual.head.len()];
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+            while let Some(u) = queue.pop_front() {
+                for arc in &residual.head[u] {
+                    let v = residual.to[*arc];
+                    if v != s && prev_arc[v].is_none() && residual.cap[*arc] > W::zero() {
+                        prev_arc[v] = Some(*arc);
+                        queue.push_back(v);
+                    }
+                }
+                if prev_arc[t].is_some() {
+                    break;
+                }
+            }
+            if prev_arc[t].is_none() {
+                break;
+            }
+            let mut path = vec![];
+            let mut v = t;
+            while let Some(arc) = prev_arc[v] {
+                path.push(arc);
+                v = residual.to[arc ^ 1];
+            }
+            value = self.add_flow(value, residual.augment(&path), s, t)?;
+        }
+        Ok(residual.into_max_flow(&self.vertices, s, value))
+    }
+
+    pub fn dinic(&self, source: T, sink: T) -> Result<MaxFlow<T, W>, GraphErr<T>> {
+        let (mut residual, s, t) = self.residual(source, sink)?;
+        let mut value = W::zero();
+        loop {
+            let mut levels = residual.levels(s);
+            if levels[t].is_none() {
+                break;
+            }
+            let mut next_arc = vec![0; residual.head.len()];
+            let mut path: Vec<usize> = vec![];
+            let mut u = s;
+            loop {
+                if u == t {
+                    value = self.add_flow(value, residual.augment(&path), s, t)?;
+                    path.clear();
+                    u = s;
+                    continue;
+                }
+                let mut advanced = false;
+                while next_arc[u] < residual.head[u].len() {
+                    let arc = residual.head[u][next_arc[u]];
+                    let v = residual.to[arc];
+                    let is_next_level = match (levels[u], levels[v]) {
+                        (Some(lu), Some(lv)) => lv == lu + 1,
+                        _ => false,
+                    };
+                    if is_next_level && residual.cap[arc] > W::zero() {
+                        path.push(arc);
+                        u = v;
+                        advanced = true;
+                        break;
+                    }
+                    next_arc[u] += 1;
+                }
+                if advanced {
+                    continue;
+                }
+                // Dead end: drop it from the level graph and retreat one arc.
+                levels[u] = None;
+                match path.pop() {
+                    Some(arc) => {
+                        u = residual.to[arc ^ 1];
+                        next_arc[u] += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+        Ok(residual.into_max_flow(&self.vertices, s, value))
+    }
+
+    fn add_flow(&self, value: W, bottleneck: W, s: usize, t: usize) -> Result<W, GraphErr<T>> {
+        checked_add(value, bottleneck)
+            .ok_or_else(|| GraphErr::with_vertices("flow overflows", self.vertices_of(&[s, t])))
+    }
+
+    fn residual(&self, source: T, sink: T) -> Result<(Residual<W>, usize, usize), GraphErr<T>> {
+        let s = self.check_vertex(&source)?;
+        let t = self.check_vertex(&sink)?;
+        if s == t {
+            return Err(GraphErr::with_vertices(
+                "source and sink must differ",
+                vec![source],
+            ));
+        }
+        let mut residual = Residual {
+            head: vec![vec![]; self.vertex_count()],
+            to: vec![],
+            cap: vec![],
+            edges: vec![],
+            directed: self.directed,
+        };
+        for (u, edges) in self.adj_list.iter().enumerate() {
+            for edge in edges {
+                let v = edge.vertex;
+                if edge.weight < W::zero() {
+                    return Err(GraphErr::with_vertices(
+                        "negative capacity",
+                        self.vertices_of(&[u, v]),
+                    ));
+                }
+                if u == v || (!self.directed && v < u) {
+                    continue;
+                }
+                let arc = residual.add_arc(u, v, edge.weight);
+                if !self.directed {
+                    residual.add_arc(v, u, edge.weight);
+                }
+                residual.edges.push((u, v, edge.weight, arc));
+            }
+        }
+        Ok((residual, s, t))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn network() -> Graph<&'static str> {
+        let mut graph = Graph::new_directed();
+        for v in &["s", "a", "b", "c", "d", "t"] {
+            graph.add_vertex(*v);
+        }
+        graph.add_edge("s", "a", 16).unwrap();
+        graph.add_edge("s", "c", 13).unwrap();
+        graph.add_edge("a", "b", 12).unwrap();
+        graph.add_edge("c", "a", 4).unwrap();
+        graph.add_edge("b", "c", 9).unwrap();
+        graph.add_edge("c", "d", 14).unwrap();
+        graph.add_edge("d", "b", 7).unwrap();
+        graph.add_edge("b", "t", 20).unwrap();
+        graph.add_edge("d", "t", 4).unwrap();
+        graph
+    }
+
+    fn check_flow(graph: &Graph<&'static str>, flow: &MaxFlow<&'static str>) {
+        assert_eq!(flow.value(), 23);
+        assert_eq!(flow.min_cut(), (&["s", "a", "c", "d"][..], &["b", "t"][..]));
+        assert_eq!(
+            flow.cut_edges(),
+            &[("a", "b", 12), ("d", "b", 7), ("d", "t", 4)]
+        );
+        assert_eq!(flow.flows().len(), graph.edge_count());
+        for v in &["a", "b", "c", "d"] {
+            let inflow: usize = flow.flows().iter().filter(|f| f.1 == *v).map(|f| f.2).sum();
+            let outflow: usize = flow.flows().iter().filter(|f| f.0 == *v).map(|f| f.2).sum();
+            assert_eq!(inflow, outflow);
+        }
+        for (v1, v2, f) in flow.flows() {
+            let capacity = graph.adj_list[graph.index[v1]]
+                .iter()
+                .find(|edge| edge.vertex == graph.index[v2])
+                .map(|edge| edge.weight)
+                .unwrap();
+            assert!(*f <= capacity);
+        }
+    }
+
+    #[test]
+    fn test_edmonds_karp() {
+        let graph = network();
+        check_flow(&graph, &graph.edmonds_karp("s", "t").unwrap());
+        assert_eq!(graph.edmonds_karp("t", "s").unwrap().value(), 0);
+        assert!(graph.edmonds_karp("s", "s").is_err());
+        assert!(graph.edmonds_karp("s", "x").is_err());
+    }
+
+    #[test]
+    fn test_dinic() {
+        let graph = network();
+        check_flow(&graph, &graph.dinic("s", "t").unwrap());
+        assert_eq!(graph.dinic("t", "s").unwrap().value(), 0);
+        assert!(graph.dinic("s", "s").is_err());
+    }
+
+    #[test]
+    fn test_undirected_flow() {
+        let mut graph = Graph::new();
+        for v in 1..=4 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(1, 2, 3.0).unwrap();
+        graph.add_edge(1, 3, 2.0).unwrap();
+        graph.add_edge(3, 2, 1.5).unwrap();
+        graph.add_edge(4, 2, 4.0).unwrap();
+        graph.add_edge(3, 4, 0.5).unwrap();
+        for flow in [graph.edmonds_karp(1, 4), graph.dinic(1, 4)].iter() {
+            let flow = flow.as_ref().unwrap();
+            assert_eq!(flow.value(), 4.5);
+            assert_eq!(flow.min_cut(), (&[1, 2, 3][..], &[4][..]));
+            assert_eq!(flow.cut_edges(), &[(2, 4, 4.0), (3, 4, 0.5)]);
+            assert!(flow.flows().contains(&(2, 4, 4.0)));
+            assert!(flow.flows().contains(&(3, 4, 0.5)));
+        }
+
+        let flow = graph.dinic(4, 1).unwrap();
+        assert_eq!(flow.value(), 4.5);
+        assert_eq!(flow.cut_edges(), &[(4, 2, 4.0), (4, 3, 0.5)]);
+
+        graph.set_weight(&1, &3, -1.0).unwrap();
+        assert!(graph.dinic(1, 4).is_err());
+
+        // flow from the higher id runs against the stored arc direction
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert(1, 2, 5);
+        graph.add_edge_or_insert(2, 3, 5);
+        for flow in [graph.edmonds_karp(3, 1), graph.dinic(3, 1)].iter() {
+            let flow = flow.as_ref().unwrap();
+            assert_eq!(flow.value(), 5);
+            assert_eq!(flow.flows(), &[(2, 1, 5), (3, 2, 5)]);
+        }
+        graph.add_edge_or_insert(1, 3, usize::MAX);
+        for flow in [graph.edmonds_karp(3, 1), graph.dinic(3, 1)].iter() {
+            assert_eq!(
+                flow.as_ref().unwrap_err(),
+                &GraphErr::with_vertices("flow overflows", vec![3, 1])
+            );
+        }
+        graph.set_weight(&1, &3, usize::MAX - 5).unwrap();
+        for flow in [graph.edmonds_karp(3, 1), graph.dinic(3, 1)].iter() {
+            let flow = flow.as_ref().unwrap();
+            assert_eq!(flow.value(), usize::MAX);
+            assert_eq!(
+                flow.flows(),
+                &[(2, 1, 5), (3, 1, usize::MAX - 5), (3, 2, 5)]
+            );
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use super::{Graph, Weight};
+use rand::distributions::uniform::SampleUniform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub struct Generator<W = usize> {
+    rng: StdRng,
+    weights: Option<(W, W)>,
+}
+
+impl Generator<usize> {
+    // Every edge gets weight 1.
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            rng: StdRng::seed_from_u64(seed),
+            weights: None,
+        }
+    }
+}
+
+impl<W> Generator<W>
+where
+    W: Weight + SampleUniform,
+{
+    // Edge weights are drawn uniformly from [low, high).
+    pub fn with_weights(seed: u64, low: W, high: W) -> Self {
+        assert!(low < high, "empty weight range");
+        Generator {
+            rng: StdRng::seed_from_u64(seed),
+            weights: Some((low, high)),
+        }
+    }
+
+    fn weight(&mut self) -> W {
+        match self.weights {
+            Some((low, high)) => self.rng.gen_range(low, high),
+            None => W::one(),
+        }
+    }
+
+    fn empty(n: usize, directed: bool) -> Graph<usize, W> {
+        let mut graph = if directed {
+            Graph::new_directed()
+        } else {
+            Graph::new()
+        };
+        for v in 0..n {
+            graph.add_vertex(v);
+        }
+        graph
+    }
+
+    fn connect(&mut self, graph: &mut Graph<usize, W>, v1: usize, v2: usize) {
+        let weight = self.weight();
+        graph.insert_edge(v1, v2, weight, ());
+    }
+
+    // Erdős–Rényi G(n, p): every possible edge is present with probability p.
+    pub fn gnp(&mut self, n: usize, p: f64, directed: bool) -> Graph<usize, W> {
+        let mut graph = Self::empty(n, directed);
+        for v1 in 0..n {
+            let first = if directed { 0 } else { v1 + 1 };
+            for v2 in first..n {
+                if v1 != v2 && self.rng.gen_bool(p) {
+                    self.connect(&mut graph, v1, v2);
+                }
+            }
+        }
+        graph
+    }
+
+    // Barabási–Albert preferential attachment: starts from a complete graph on
+    // m + 1 vertices, then links every new vertex to m distinct existing ones
+    // chosen with probability proportional to their degree.
+    pub fn barabasi_albert(&mut self, n: usize, m: usize) -> Graph<usize, W> {
+        assert!(m > 0, "each new vertex needs at least one edge");
+        let mut graph = Self::empty(n, false);
+        let seed = n.min(m + 1);
+        // every vertex appears here once per incident edge
+        let mut endpoints = vec![];
+        for v1 in 0..seed {
+            for v2 in v1 + 1..seed {
+                self.connect(&mut graph, v1, v2);
+                endpoints.push(v1);
+                endpoints.push(v2);
+            }
+        }
+        for v in seed..n {
+            let mut targets: Vec<usize> = vec![];
+            while targets.len() < m {
+                let target = endpoints[self.rng.gen_range(0, endpoints.len())];
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+            for target in targets {
+                self.connect(&mut graph, v, target);
+                endpoints.push(v);
+                endpoints.push(target);
+            }
+        }
+        graph
+    }
+
+    // Undirected rows x cols lattice with vertices (row, col) in row-major
+    // order; diagonals add the two diagonal moves out of every cell.
+    pub fn grid(&mut self, rows: usize, cols: usize, diagonals: bool) -> Graph<(usize, usize), W> {
+        let mut graph = Graph::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                graph.add_vertex((row, col));
+            }
+        }
+        let id = |row: usize, col: usize| row * cols + col;
+        for row in 0..rows {
+            for col in 0..cols {
+                let mut moves = vec![(row, col + 1), (row + 1, col)];
+                if diagonals {
+                    moves.push((row + 1, col + 1));
+                    if col > 0 {
+                        moves.push((row + 1, col - 1));
+                    }
+                }
+                for (r, c) in moves {
+                    if r < rows && c < cols {
+                        let weight = self.weight();
+                        graph.insert_edge(id(row, col), id(r, c), weight, ());
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    // Directed acyclic graph: vertices are shuffled into a hidden topological
+    // order and every forward edge of that order is present with probability p.
+    pub fn dag(&mut self, n: usize, p: f64) -> Graph<usize, W> {
+        let mut graph = Self::empty(n, true);
+        let mut order: Vec<usize> = (0..n).collect();
+        self.rng.shuffle(&mut order);
+        for i in 0..n {
+            for j in i + 1..n {
+                if self.rng.gen_bool(p) {
+                    self.connect(&mut graph, order[i], order[j]);
+                }
+            }
+        }
+        graph
+    }
+
+    // Uniformly random labelled tree on n vertices, decoded from a random
+    // Prüfer sequence.
+    pub fn tree(&mut self, n: usize) -> Graph<usize, W> {
+        let mut graph = Self::empty(n, false);
+        if n < 2 {
+            return graph;
+        }
+        let sequence: Vec<usize> = (0..n - 2).map(|_| self.rng.gen_range(0, n)).collect();
+        let mut degree = vec![1; n];
+        for v in &sequence {
+            degree[*v] += 1;
+        }
+        let mut leaves: BinaryHeap<Reverse<usize>> =
+            (0..n).filter(|v| degree[*v] == 1).map(Reverse).collect();
+        for v in sequence {
+            let Reverse(leaf) = leaves.pop().unwrap();
+            self.connect(&mut graph, leaf, v);
+            degree[v] -= 1;
+            if degree[v] == 1 {
+                leaves.push(Reverse(v));
+            }
+        }
+        let Reverse(v1) = leaves.pop().unwrap();
+        let Reverse(v2) = leaves.pop().unwrap();
+        self.connect(&mut graph, v1, v2);
+        graph
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generators_are_seeded() {
+        let mut a = Generator::with_weights(42, 1, 10);
+        let mut b = Generator::with_weights(42, 1, 10);
+        assert_eq!(
+            a.gnp(30, 0.2, true).unique_edges(),
+            b.gnp(30, 0.2, true).unique_edges()
+        );
+        assert_eq!(
+            a.barabasi_albert(40, 2).unique_edges(),
+            b.barabasi_albert(40, 2).unique_edges()
+        );
+        assert_eq!(a.dag(25, 0.3).unique_edges(), b.dag(25, 0.3).unique_edges());
+        assert_eq!(a.tree(50).unique_edges(), b.tree(50).unique_edges());
+        let mut c = Generator::with_weights(43, 1, 10);
+        assert_ne!(
+            Generator::with_weights(42, 1, 10)
+                .gnp(30, 0.2, false)
+                .unique_edges(),
+            c.gnp(30, 0.2, false).unique_edges()
+        );
+    }
+
+    #[test]
+    fn test_generator_shapes() {
+        let mut generator = Generator::new(7);
+        assert_eq!(generator.gnp(10, 0.0, false).edge_count(), 0);
+        assert_eq!(generator.gnp(10, 1.0, false).edge_count(), 45);
+        assert_eq!(generator.gnp(10, 1.0, true).edge_count(), 90);
+
+        let graph = generator.barabasi_albert(100, 3);
+        assert_eq!(graph.edge_count(), 6 + 96 * 3);
+        assert_eq!(graph.connected_components().unwrap().len(), 1);
+        assert!((0..100).all(|v| graph.out_degree(&v) >= 3));
+
+        let grid = generator.grid(3, 4, false);
+        assert_eq!(grid.vertex_count(), 12);
+        assert_eq!(grid.edge_count(), 3 * 3 + 2 * 4);
+        assert_eq!(
+            grid.neighbors(&(1, 1)),
+            vec![(0, 1), (1, 0), (1, 2), (2, 1)]
+        );
+        let grid = generator.grid(3, 4, true);
+        assert_eq!(grid.edge_count(), 17 + 2 * 2 * 3);
+        assert_eq!(grid.out_degree(&(1, 1)), 8);
+
+        let dag = generator.dag(40, 0.3);
+        assert!(!dag.has_cycle());
+        assert_eq!(dag.topological_sort().unwrap().len(), 40);
+
+        for n in 0..20 {
+            let tree = generator.tree(n);
+            assert_eq!(tree.vertex_count(), n);
+            assert_eq!(tree.edge_count(), n.saturating_sub(1));
+            assert!(n == 0 || tree.bfs(0).len() == n);
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_matches_bfs_on_unit_weights() {
+        let mut generator = Generator::new(1);
+        for _ in 0..10 {
+            let graph = generator.gnp(60, 0.05, true);
+            let paths = graph.dijkstra_all(0).unwrap();
+            for visit in graph.bfs_iter(0) {
+                assert_eq!(paths.distance(visit.vertex()), Some(visit.depth()));
+            }
+            assert_eq!(paths.distances().len(), graph.bfs(0).len());
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use super::shortest_path::is_shorter;
+use super::{checked_add, Graph, GraphErr, Weight};
+use std::hash::Hash;
+
+// The DP table has 2^n * n entries, which is about 20 million at this size.
+pub const MAX_HAMILTONIAN_VERTICES: usize = 20;
+
+const UNREACHED: u8 = u8::MAX;
+// Reached only by paths whose cost does not fit in `W`.
+const TOO_LONG: u8 = u8::MAX - 1;
+
+// Best cost of a path over the vertices in each bitmask, per end vertex, with
+// the vertex visited just before that end.
+struct Table<W> {
+    n: usize,
+    cost: Vec<W>,
+    prev: Vec<u8>,
+}
+
+impl<W: Weight> Table<W> {
+    fn cell(&self, mask: usize, v: usize) -> usize {
+        mask * self.n + v
+    }
+
+    fn get(&self, mask: usize, v: usize) -> Option<W> {
+        let cell = self.cell(mask, v);
+        if self.prev[cell] >= TOO_LONG {
+            None
+        } else {
+            Some(self.cost[cell])
+        }
+    }
+
+    fn too_long(&self, mask: usize, v: usize) -> bool {
+        self.prev[self.cell(mask, v)] == TOO_LONG
+    }
+
+    fn set_too_long(&mut self, mask: usize, v: usize) {
+        let cell = self.cell(mask, v);
+        if self.prev[cell] == UNREACHED {
+            self.prev[cell] = TOO_LONG;
+        }
+    }
+
+    fn path(&self, mut mask: usize, mut v: usize) -> Vec<usize> {
+        let mut path = vec![v];
+        while mask != 1 << v {
+            let prev = self.prev[self.cell(mask, v)] as usize;
+            mask ^= 1 << v;
+            v = prev;
+            path.push(v);
+        }
+        path.reverse();
+        path
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    // Cheapest path that visits every vertex once, between any two vertices.
+    pub fn hamiltonian_path(&self) -> Result<Option<(W, Vec<T>)>, GraphErr<T>> {
+        if self.vertex_count() == 0 {
+            return Ok(Some((W::zero(), vec![])));
+        }
+        let starts: Vec<usize> = (0..self.vertex_count()).collect();
+        let table = self.hamiltonian_table(&starts)?;
+        let full = (1 << self.vertex_count()) - 1;
+        let mut best: Option<(W, usize)> = None;
+        for v in 0..self.vertex_count() {
+            if let Some(cost) = table.get(full, v) {
+                if is_shorter(cost, best.map(|(best_cost, _)| best_cost)) {
+                    best = Some((cost, v));
+                }
+            }
+        }
+        if best.is_none() {
+            let ends: Vec<usize> = (0..self.vertex_count())
+                .filter(|v| table.too_long(full, *v))
+                .collect();
+            if !ends.is_empty() {
+                return Err(GraphErr::with_vertices(
+                    "path weight overflows",
+                    self.vertices_of(&ends),
+                ));
+            }
+        }
+        Ok(best.map(|(cost, end)| (cost, self.vertices_of(&table.path(full, end)))))
+    }
+
+    // Cheapest closed tour from `start` through every other vertex and back;
+    // the returned path begins and ends with `start`.
+    pub fn travelling_salesman(&self, start: T) -> Result<Option<(W, Vec<T>)>, GraphErr<T>> {
+        let start = self.check_vertex(&start)?;
+        let table = self.hamiltonian_table(&[start])?;
+        let weights = self.min_weights();
+        let full = (1 << self.vertex_count()) - 1;
+        let mut best: Option<(W, usize)> = None;
+        let mut too_long = false;
+        for (v, weights) in weights.iter().enumerate() {
+            let back = if v == start {
+                // only a single vertex tour ends where it starts
+                if self.vertex_count() == 1 {
+                    Some(W::zero())
+                } else {
+                    None
+                }
+            } else {
+                weights[start]
+            };
+            let back = match back {
+                Some(back) => back,
+                None => continue,
+            };
+            let cost = match table.get(full, v) {
+                Some(cost) => cost,
+                None => {
+                    too_long |= table.too_long(full, v);
+                    continue;
+                }
+            };
+            match checked_add(cost, back) {
+                Some(cost) if is_shorter(cost, best.map(|(best_cost, _)| best_cost)) => {
+                    best = Some((cost, v));
+                }
+                Some(_) => {}
+                None if back < W::zero() => {
+                    return Err(GraphErr::with_vertices(
+                        "path weight overflows",
+                        self.vertices_of(&[v, start]),
+                    ))
+                }
+                None => too_long = true,
+            }
+        }
+        if best.is_none() && too_long {
+            return Err(GraphErr::with_vertices(
+                "path weight overflows",
+                self.vertices_of(&[start]),
+            ));
+        }
+        Ok(best.map(|(cost, end)| {
+            let mut tour = table.path(full, end);
+            if end != start {
+                tour.push(start);
+            }
+            (cost, self.vertices_of(&tour))
+        }))
+    }
+
+    // Lightest edge between each ordered pair of distinct vertices.
+    fn min_weights(&self) -> Vec<Vec<Option<W>>> {
+        let mut weights = vec![vec![None; self.vertex_count()]; self.vertex_count()];
+        for (v, edges) in self.adj_list.iter().enumerate() {
+            for edge in edges {
+                let weight = &mut weights[v][edge.vertex];
+                if v != edge.vertex && is_shorter(edge.weight, *weight) {
+                    *weight = Some(edge.weight);
+                }
+            }
+        }
+        weights
+    }
+
+    fn hamiltonian_table(&self, starts: &[usize]) -> Result<Table<W>, GraphErr<T>> {
+        let n = self.vertex_count();
+        if n > MAX_HAMILTONIAN_VERTICES {
+            return Err(GraphErr::new(&format!(
+                "hamiltonian search supports at most {} vertices, got {}",
+                MAX_HAMILTONIAN_VERTICES, n
+            )));
+        }
+        let weights = self.min_weights();
+        let mut table = Table {
+            n,
+            cost: vec![W::zero(); n << n],
+            prev: vec![UNREACHED; n << n],
+        };
+        for start in starts {
+            // a start points at itself, which is never a valid predecessor
+            let cell = table.cell(1 << start, *start);
+            table.prev[cell] = *start as u8;
+        }
+        for mask in 1..1usize << n {
+            for (v, neighbors) in weights.iter().enumerate() {
+                if mask & 1 << v == 0 {
+                    continue;
+                }
+                // a path too long for `W` only makes its extensions too long
+                let cost = table.get(mask, v);
+                if cost.is_none() && !table.too_long(mask, v) {
+                    continue;
+                }
+                for (w, weight) in neighbors.iter().enumerate() {
+                    let weight = match weight {
+                        Some(weight) if mask & 1 << w == 0 => *weight,
+                        _ => continue,
+                    };
+                    let next = mask | 1 << w;
+                    match cost.map(|cost| checked_add(cost, weight)) {
+                        Some(Some(new_cost)) => {
+                            if is_shorter(new_cost, table.get(next, w)) {
+                                let cell = table.cell(next, w);
+                                table.cost[cell] = new_cost;
+                                table.prev[cell] = v as u8;
+                            }
+                        }
+                        Some(None) if weight < W::zero() => {
+                            return Err(GraphErr::with_vertices(
+                                "path weight overflows",
+                                self.vertices_of(&[v, w]),
+                            ))
+                        }
+                        _ => table.set_too_long(next, w),
+                    }
+                }
+            }
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_travelling_salesman() {
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert("a", "b", 10);
+        graph.add_edge_or_insert("a", "c", 15);
+        graph.add_edge_or_insert("a", "d", 20);
+        graph.add_edge_or_insert("b", "c", 35);
+        graph.add_edge_or_insert("b", "d", 25);
+        graph.add_edge_or_insert("c", "d", 30);
+        let (cost, tour) = graph.travelling_salesman("a").unwrap().unwrap();
+        assert_eq!(cost, 80);
+        assert_eq!(tour.len(), 5);
+        assert_eq!((tour[0], tour[4]), ("a", "a"));
+        assert_eq!(graph.travelling_salesman("c").unwrap().unwrap().0, 80);
+
+        // 'e' hangs off 'a' only, so there is no tour but there is a path
+        graph.add_edge_or_insert("a", "e", 1);
+        assert_eq!(graph.travelling_salesman("a").unwrap(), None);
+        let (cost, path) = graph.hamiltonian_path().unwrap().unwrap();
+        assert_eq!(cost, 1 + 10 + 25 + 30);
+        assert_eq!(path, vec!["e", "a", "b", "d", "c"]);
+        assert!(graph.travelling_salesman("z").is_err());
+
+        let mut single = Graph::new();
+        single.add_vertex("a");
+        assert_eq!(
+            single.travelling_salesman("a").unwrap(),
+            Some((0, vec!["a"]))
+        );
+    }
+
+    #[test]
+    fn test_hamiltonian_overflow() {
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert(0, 1, u8::MAX);
+        graph.add_edge_or_insert(1, 2, 1);
+        graph.add_edge_or_insert(2, 0, 1);
+        assert_eq!(graph.hamiltonian_path().unwrap(), Some((2, vec![1, 2, 0])));
+        assert_eq!(
+            graph.travelling_salesman(0).unwrap_err(),
+            GraphErr::with_vertices("path weight overflows", vec![0])
+        );
+        graph.add_edge_or_insert(0, 2, 1);
+        graph.add_edge_or_insert(1, 0, 1);
+        graph.add_edge_or_insert(2, 1, 1);
+        assert_eq!(
+            graph.travelling_salesman(0).unwrap(),
+            Some((3, vec![0, 2, 1, 0]))
+        );
+    }
+
+    #[test]
+    fn test_hamiltonian_path_directed() {
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert(0, 1, 1);
+        graph.add_edge_or_insert(1, 2, 1);
+        graph.add_edge_or_insert(2, 0, 1);
+        graph.add_edge_or_insert(3, 0, 5);
+        graph.add_edge_or_insert(3, 1, 1);
+        assert_eq!(
+            graph.hamiltonian_path().unwrap(),
+            Some((3, vec![3, 1, 2, 0]))
+        );
+        graph.add_vertex(4);
+        assert_eq!(graph.hamiltonian_path().unwrap(), None);
+        assert_eq!(
+            Graph::<i32>::new().hamiltonian_path().unwrap(),
+            Some((0, vec![]))
+        );
+
+        let mut large = Graph::new();
+        for v in 0..=MAX_HAMILTONIAN_VERTICES {
+            large.add_edge_or_insert(v, (v + 1) % (MAX_HAMILTONIAN_VERTICES + 1), 1);
+        }
+        let err = large.hamiltonian_path().unwrap_err();
+        assert_eq!(
+            err.msg(),
+            "hamiltonian search supports at most 20 vertices, got 21"
+        );
+    }
+}
@@ -0,0 +1,779 @@
+use super::{Graph, GraphErr, Weight};
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Id(&'a str),
+    EdgeOp(&'a str),
+    Punct(char),
+}
+
+type Spanned<'a> = (Token<'a>, usize, usize);
+type Fields = Vec<(usize, String)>;
+
+struct Parser<'a> {
+    tokens: Vec<Spanned<'a>>,
+    pos: usize,
+    end: (usize, usize),
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Result<Self, GraphErr<String>> {
+        let mut tokens = vec![];
+        let mut chars = input.char_indices().peekable();
+        let (mut line, mut column) = (1, 1);
+        while let Some((start, c)) = chars.next() {
+            let (token_line, token_column) = (line, column);
+            column += 1;
+            match c {
+                '\n' => {
+                    line += 1;
+                    column = 1;
+                }
+                c if c.is_whitespace() => {}
+                '#' => {
+                    while matches!(chars.peek(), Some((_, c)) if *c != '\n') {
+                        chars.next();
+                    }
+                }
+                '/' if chars.peek().map(|(_, c)| *c) == Some('/') => {
+                    while matches!(chars.peek(), Some((_, c)) if *c != '\n') {
+                        chars.next();
+                    }
+                }
+                '/' if chars.peek().map(|(_, c)| *c) == Some('*') => {
+                    chars.next();
+                    column += 1;
+                    let mut prev = ' ';
+                    loop {
+                        match chars.next() {
+                            Some((_, '\n')) => {
+                                line += 1;
+                                column = 1;
+                            }
+                            Some((_, c)) => {
+                                column += 1;
+                                if prev == '*' && c == '/' {
+                                    break;
+                                }
+                                prev = c;
+                            }
+                            None => {
+                                return Err(GraphErr::at(
+                                    "unterminated comment",
+                                    token_line,
+                                    token_column,
+                                ))
+                            }
+                        }
+                    }
+                }
+                '"' => {
+                    let mut escaped = false;
+                    let end = loop {
+                        match chars.next() {
+                            Some((idx, '"')) if !escaped => break idx,
+                            Some((_, c)) => {
+                                escaped = !escaped && c == '\\';
+                                if c == '\n' {
+                                    line += 1;
+                                    column = 1;
+                                } else {
+                                    column += 1;
+                                }
+                            }
+                            None => {
+                                return Err(GraphErr::at(
+                                    "unterminated string",
+                                    token_line,
+                                    token_column,
+                                ))
+                            }
+                        }
+                    };
+                    column += 1;
+                    tokens.push((Token::Id(&input[start + 1..end]), token_line, token_column));
+                }
+                '-' if matches!(chars.peek(), Some((_, '-')) | Some((_, '>'))) => {
+                    chars.next();
+                    column += 1;
+                    tokens.push((
+                        Token::EdgeOp(&input[start..start + 2]),
+                        token_line,
+                        token_column,
+                    ));
+                }
+                '{' | '}' | '[' | ']' | '=' | ';' | ',' | ':' => {
+                    tokens.push((Token::Punct(c), token_line, token_column));
+                }
+                c if is_id_char(c) || c == '-' => {
+                    let mut end = start + c.len_utf8();
+                    while let Some((idx, c)) = chars.peek().copied() {
+                        if !is_id_char(c) {
+                            break;
+                        }
+                        chars.next();
+                        column += 1;
+                        end = idx + c.len_utf8();
+                    }
+                    tokens.push((Token::Id(&input[start..end]), token_line, token_column));
+                }
+                _ => {
+                    return Err(GraphErr::at(
+                        "unexpected character",
+                        token_line,
+                        token_column,
+                    ))
+                }
+            }
+        }
+        Ok(Parser {
+            tokens,
+            pos: 0,
+            end: (line, column),
+        })
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).map(|(token, _, _)| *token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<Token<'a>> {
+        self.tokens
+            .get(self.pos + offset)
+            .map(|(token, _, _)| *token)
+    }
+
+    fn next(&mut self) -> Option<Spanned<'a>> {
+        let token = self.tokens.get(self.pos).copied();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, msg: &str) -> GraphErr<String> {
+        match self.tokens.get(self.pos) {
+            Some((_, line, column)) => GraphErr::at(msg, *line, *column),
+            None => GraphErr::at(msg, self.end.0, self.end.1),
+        }
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<(), GraphErr<String>> {
+        match self.peek() {
+            Some(Token::Punct(p)) if p == c => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.error(&format!("expected '{}'", c))),
+        }
+    }
+
+    fn expect_id(&mut self) -> Result<Spanned<'a>, GraphErr<String>> {
+        match self.peek() {
+            Some(Token::Id(_)) => Ok(self.next().unwrap()),
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    fn attrs(&mut self) -> Result<Vec<(&'a str, Spanned<'a>)>, GraphErr<String>> {
+        let mut attrs = vec![];
+        while self.peek() == Some(Token::Punct('[')) {
+            self.pos += 1;
+            loop {
+                match self.peek() {
+                    Some(Token::Punct(']')) => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(Token::Punct(',')) | Some(Token::Punct(';')) => self.pos += 1,
+                    _ => {
+                        if let (Token::Id(key), _, _) = self.expect_id()? {
+                            self.expect_punct('=')?;
+                            attrs.push((key, self.expect_id()?));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(attrs)
+    }
+
+    fn skip_port(&mut self) -> Result<(), GraphErr<String>> {
+        while self.peek() == Some(Token::Punct(':')) {
+            self.pos += 1;
+            self.expect_id()?;
+        }
+        Ok(())
+    }
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+// Whitespace separated fields with their columns, up to a `#` comment. A
+// field is quoted as in `quote` when it holds whitespace, `"` or `#`.
+fn fields(line: &str, number: usize) -> Result<Fields, GraphErr<String>> {
+    let mut fields = vec![];
+    let mut chars = line.chars().enumerate().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if c == '#' {
+            break;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut field = String::new();
+        if c == '"' {
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) if matches!(chars.peek(), Some((_, '"')) | Some((_, '\\'))) => {
+                        field.extend(chars.next().map(|(_, c)| c))
+                    }
+                    Some((_, c)) => field.push(c),
+                    None => return Err(GraphErr::at("unterminated string", number, idx + 1)),
+                }
+            }
+        } else {
+            field.push(c);
+            while let Some((_, c)) = chars.peek() {
+                if c.is_whitespace() || *c == '#' {
+                    break;
+                }
+                field.push(*c);
+                chars.next();
+            }
+        }
+        fields.push((idx + 1, field));
+    }
+    Ok(fields)
+}
+
+fn content_lines(
+    input: &str,
+) -> impl Iterator<Item = Result<(usize, Fields), GraphErr<String>>> + '_ {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| match fields(line, idx + 1) {
+            Ok(fields) if fields.is_empty() => None,
+            fields => Some(fields.map(|fields| (idx + 1, fields))),
+        })
+}
+
+// Quotes only the names `fields` would otherwise split or cut short.
+fn field<T: Display>(vertex: &T) -> String {
+    let field = vertex.to_string();
+    if field.is_empty() || field.contains(|c: char| c.is_whitespace() || c == '"' || c == '#') {
+        quote(&field)
+    } else {
+        field
+    }
+}
+
+fn parse_weight<T, W: FromStr>(value: &str, line: usize, column: usize) -> Result<W, GraphErr<T>> {
+    value
+        .parse()
+        .map_err(|_| GraphErr::at("invalid weight", line, column))
+}
+
+// Parsed vertices are owned, since quoted names may need unescaping.
+impl<W> Graph<String, W>
+where
+    W: Weight + FromStr,
+{
+    pub fn from_dot(input: &str) -> Result<Self, GraphErr<String>> {
+        let mut parser = Parser::new(input)?;
+        if let Some(Token::Id(strict)) = parser.peek() {
+            if strict.eq_ignore_ascii_case("strict") {
+                parser.pos += 1;
+            }
+        }
+        let mut graph = match parser.peek() {
+            Some(Token::Id(kind)) if kind.eq_ignore_ascii_case("graph") => Graph::new(),
+            Some(Token::Id(kind)) if kind.eq_ignore_ascii_case("digraph") => Graph::new_directed(),
+            _ => return Err(parser.error("expected 'graph' or 'digraph'")),
+        };
+        parser.pos += 1;
+        if let Some(Token::Id(_)) = parser.peek() {
+            parser.pos += 1;
+        }
+        parser.expect_punct('{')?;
+
+        loop {
+            match parser.peek() {
+                None => return Err(parser.error("unexpected end of input")),
+                Some(Token::Punct('}')) => {
+                    parser.pos += 1;
+                    break;
+                }
+                Some(Token::Punct(';')) => parser.pos += 1,
+                Some(Token::Id(keyword))
+                    if ["graph", "node", "edge"]
+                        .iter()
+                        .any(|k| keyword.eq_ignore_ascii_case(k))
+                        && parser.peek_at(1) == Some(Token::Punct('[')) =>
+                {
+                    parser.pos += 1;
+                    parser.attrs()?;
+                }
+                Some(Token::Id(keyword)) if keyword.eq_ignore_ascii_case("subgraph") => {
+                    return Err(parser.error("subgraphs are not supported"));
+                }
+                Some(Token::Punct('{')) => {
+                    return Err(parser.error("subgraphs are not supported"));
+                }
+                Some(Token::Id(_)) => {
+                    let (vertex, _, _) = parser.expect_id()?;
+                    if parser.peek() == Some(Token::Punct('=')) {
+                        parser.pos += 1;
+                        parser.expect_id()?;
+                        continue;
+                    }
+                    parser.skip_port()?;
+                    let mut chain = vec![vertex];
+                    while let Some(Token::EdgeOp(op)) = parser.peek() {
+                        if (op == "->") != graph.directed {
+                            return Err(parser.error("edge operator does not match graph kind"));
+                        }
+                        parser.pos += 1;
+                        let (vertex, _, _) = parser.expect_id()?;
+                        parser.skip_port()?;
+                        chain.push(vertex);
+                    }
+                    let attrs = parser.attrs()?;
+                    // Labels are free text and Graphviz wants `weight` to be a
+                    // non-negative integer, so `to_dot` writes `cost`;
+                    // `weight` is still read from files written elsewhere.
+                    let attr = |name| attrs.iter().find(|(key, _)| *key == name);
+                    let weight = match attr("cost").or_else(|| attr("weight")) {
+                        Some((_, (Token::Id(value), line, column))) => {
+                            parse_weight(value, *line, *column)?
+                        }
+                        _ => W::one(),
+                    };
+                    let ids: Vec<String> = chain
+                        .iter()
+                        .filter_map(|token| match token {
+                            Token::Id(id) => Some(unescape(id)),
+                            _ => None,
+                        })
+                        .collect();
+                    if ids.len() == 1 {
+                        graph.add_vertex(ids[0].clone());
+                    }
+                    for pair in ids.windows(2) {
+                        graph.add_edge_or_insert(pair[0].clone(), pair[1].clone(), weight);
+                    }
+                }
+                _ => return Err(parser.error("unexpected token")),
+            }
+        }
+        if parser.peek().is_some() {
+            return Err(parser.error("unexpected token after graph"));
+        }
+        Ok(graph)
+    }
+
+    pub fn from_edge_list(input: &str, directed: bool) -> Result<Self, GraphErr<String>> {
+        let mut graph = if directed {
+            Graph::new_directed()
+        } else {
+            Graph::new()
+        };
+        for fields in content_lines(input) {
+            let (line, fields) = fields?;
+            match &fields[..] {
+                [(_, v)] => graph.add_vertex(v.clone()),
+                [(_, v1), (_, v2)] => graph.add_edge_or_insert(v1.clone(), v2.clone(), W::one()),
+                [(_, v1), (_, v2), (column, weight)] => {
+                    let weight = parse_weight(weight, line, *column)?;
+                    graph.add_edge_or_insert(v1.clone(), v2.clone(), weight);
+                }
+                _ => return Err(GraphErr::at("unexpected field", line, fields[3].0)),
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn from_adjacency_matrix(input: &str, directed: bool) -> Result<Self, GraphErr<String>> {
+        let mut graph = if directed {
+            Graph::new_directed()
+        } else {
+            Graph::new()
+        };
+        let mut lines = content_lines(input);
+        let labels: Vec<String> = match lines.next().transpose()? {
+            Some((line, fields)) => {
+                for (i, (column, label)) in fields.iter().enumerate() {
+                    if fields[..i].iter().any(|(_, other)| other == label) {
+                        return Err(GraphErr::at("duplicate vertex", line, *column));
+                    }
+                    graph.add_vertex(label.clone());
+                }
+                fields.into_iter().map(|(_, label)| label).collect()
+            }
+            None => return Ok(graph),
+        };
+
+        let mut rows: Vec<Vec<Option<W>>> = vec![];
+        let mut positions = vec![];
+        for fields in lines {
+            let (line, fields) = fields?;
+            if rows.len() == labels.len() {
+                return Err(GraphErr::at("unexpected row", line, fields[0].0));
+            }
+            if fields.len() != labels.len() {
+                let column = match fields.get(labels.len()) {
+                    Some((column, _)) => *column,
+                    None => fields[fields.len() - 1].0,
+                };
+                return Err(GraphErr::at(
+                    &format!("expected {} entries", labels.len()),
+                    line,
+                    column,
+                ));
+            }
+            let mut row = vec![];
+            let mut row_positions = vec![];
+            for (column, entry) in fields {
+                row.push(match entry.as_str() {
+                    "-" => None,
+                    weight => Some(parse_weight(weight, line, column)?),
+                });
+                row_positions.push((line, column));
+            }
+            rows.push(row);
+            positions.push(row_positions);
+        }
+        if rows.len() < labels.len() {
+            let line = input.lines().count().max(1);
+            return Err(GraphErr::at(
+                &format!("expected {} rows", labels.len()),
+                line,
+                1,
+            ));
+        }
+
+        for i in 0..labels.len() {
+            for j in 0..labels.len() {
+                if !directed && j < i {
+                    if rows[i][j] != rows[j][i] {
+                        let (line, column) = positions[i][j];
+                        return Err(GraphErr::at("matrix is not symmetric", line, column));
+                    }
+                    continue;
+                }
+                if let Some(weight) = rows[i][j] {
+                    graph.add_edge_or_insert(labels[i].clone(), labels[j].clone(), weight);
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone + Display,
+    W: Weight + Display,
+{
+    pub fn to_edge_list(&self) -> String {
+        let mut output = String::new();
+        let edges = self.unique_edges();
+        for (v1, v2, weight) in &edges {
+            let (v1, v2) = (&self.vertices[*v1], &self.vertices[*v2]);
+            writeln!(output, "{} {} {}", field(v1), field(v2), weight).unwrap();
+        }
+        for (v, vertex) in self.vertices.iter().enumerate() {
+            if !edges.iter().any(|(v1, v2, _)| *v1 == v || *v2 == v) {
+                writeln!(output, "{}", field(vertex)).unwrap();
+            }
+        }
+        output
+    }
+
+    pub fn to_adjacency_matrix(&self) -> String {
+        let labels: Vec<String> = self.vertices.iter().map(field).collect();
+        let mut output = labels.join(" ");
+        output.push('\n');
+        for edges in &self.adj_list {
+            let row: Vec<String> = (0..self.vertex_count())
+                .map(|v2| {
+                    let mut weights = edges
+                        .iter()
+                        .filter(|edge| edge.vertex == v2)
+                        .map(|edge| edge.weight);
+                    let first = weights.next();
+                    match weights.fold(first, |min, weight| match min {
+                        Some(min) if min <= weight => Some(min),
+                        _ => Some(weight),
+                    }) {
+                        Some(weight) => weight.to_string(),
+                        None => "-".to_string(),
+                    }
+                })
+                .collect();
+            output.push_str(&row.join(" "));
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighted(&[])
+    }
+
+    pub fn to_dot_highlighted(&self, path: &[T]) -> String {
+        let (kind, op) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let path: Vec<Option<usize>> = path.iter().map(|v| self.index.get(v).copied()).collect();
+        let on_path = |v1: usize, v2: usize| {
+            path.windows(2).any(|pair| {
+                (pair[0] == Some(v1) && pair[1] == Some(v2))
+                    || (!self.directed && pair[0] == Some(v2) && pair[1] == Some(v1))
+            })
+        };
+        let mut output = format!("{} {{\n", kind);
+        for (v, vertex) in self.vertices.iter().enumerate() {
+            if path.contains(&Some(v)) {
+                writeln!(output, "    {} [color=red];", quote(vertex)).unwrap();
+            } else {
+                writeln!(output, "    {};", quote(vertex)).unwrap();
+            }
+        }
+        for (v1, v2, weight) in self.unique_edges() {
+            let highlight = if on_path(v1, v2) {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            };
+            writeln!(
+                output,
+                "    {} {} {} [label=\"{}\", cost=\"{}\"{}];",
+                quote(&self.vertices[v1]),
+                op,
+                quote(&self.vertices[v2]),
+                weight,
+                weight,
+                highlight
+            )
+            .unwrap();
+        }
+        output.push_str("}\n");
+        output
+    }
+}
+
+fn quote<T: Display>(vertex: &T) -> String {
+    let vertex = vertex.to_string().replace('\\', "\\\\");
+    format!("\"{}\"", vertex.replace('"', "\\\""))
+}
+
+// Reverses `quote`; any other backslash is kept as written.
+fn unescape(id: &str) -> String {
+    let mut unescaped = String::with_capacity(id.len());
+    let mut chars = id.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('"')) | ('\\', Some('\\')) => unescaped.extend(chars.next()),
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_dot() {
+        let input = r#"
+            // delivery routes
+            strict digraph routes {
+                graph [rankdir=LR];
+                node [shape=circle]
+                rankdir = LR;
+                depot;
+                "north side" [color=blue];
+                depot -> a -> b [weight=3];
+                a -> "north side" [label="main road", weight=2.5, color=red];
+                /* a comment
+                   spanning lines */
+                b:e -> depot:w;
+                # preprocessor-style comment
+            }
+        "#;
+        let graph: Graph<String, f64> = Graph::from_dot(input).unwrap();
+        let v = |name: &str| name.to_string();
+        assert!(graph.is_directed());
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(
+            graph.dijkstra(v("depot"), v("north side")),
+            Ok(Some((5.5, vec![v("depot"), v("a"), v("north side")])))
+        );
+        assert_eq!(
+            graph.dijkstra(v("b"), v("depot")),
+            Ok(Some((1.0, vec![v("b"), v("depot")])))
+        );
+
+        let graph: Graph<String> = Graph::from_dot("graph { 1 -- 2 -- 3; 3 -- 1 }").unwrap();
+        assert!(!graph.is_directed());
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.contains_edge(&v("1"), &v("3")));
+    }
+
+    #[test]
+    fn test_dot_errors() {
+        let parse = |input| Graph::<String, i32>::from_dot(input).unwrap_err();
+        let err = parse("digraph {\n  a -> b;\n  b -- c;\n}");
+        assert_eq!(err.msg(), "edge operator does not match graph kind");
+        assert_eq!((err.line(), err.column()), (Some(3), Some(5)));
+        assert_eq!(
+            err.to_string(),
+            "edge operator does not match graph kind at line 3, column 5"
+        );
+
+        let err = parse("graph {\n  a -- b [weight=heavy];\n}");
+        assert_eq!(err.msg(), "invalid weight");
+        assert_eq!((err.line(), err.column()), (Some(2), Some(18)));
+
+        let err = parse("graph {\n  a -- b\n");
+        assert_eq!(err.msg(), "unexpected end of input");
+        assert_eq!(err.line(), Some(3));
+
+        let err = parse("tree { a }");
+        assert_eq!((err.line(), err.column()), (Some(1), Some(1)));
+        let err = parse("graph { a -- \"b }");
+        assert_eq!(err.msg(), "unterminated string");
+        assert_eq!(err.column(), Some(14));
+        assert_eq!(parse("graph { subgraph { a } }").column(), Some(9));
+        assert_eq!(
+            parse("graph { a -- b } c").msg(),
+            "unexpected token after graph"
+        );
+        assert_eq!(parse("graph { a @ b }").column(), Some(11));
+    }
+
+    #[test]
+    fn test_edge_list() {
+        let input = "# from to weight\nA B 4\nA C 2\n\nC D -1  # refund\nD E\nF\n";
+        let graph: Graph<String, i32> = Graph::from_edge_list(input, true).unwrap();
+        assert_eq!(graph.vertex_count(), 6);
+        assert_eq!(graph.edge_count(), 4);
+        let paths = graph.bellman_ford("A".to_string()).unwrap();
+        assert_eq!(paths.distance("E"), Some(2));
+        assert_eq!(graph.to_edge_list(), "A B 4\nA C 2\nC D -1\nD E 1\nF\n");
+
+        let parse = |input| Graph::<String, i32>::from_edge_list(input, false).unwrap_err();
+        let err = parse("A B 1\nA  C  x\n");
+        assert_eq!(err.msg(), "invalid weight");
+        assert_eq!((err.line(), err.column()), (Some(2), Some(7)));
+        let err = parse("A B 1 2");
+        assert_eq!((err.line(), err.column()), (Some(1), Some(7)));
+        let err = parse("A \"B 1");
+        assert_eq!(err.msg(), "unterminated string");
+        assert_eq!((err.line(), err.column()), (Some(1), Some(3)));
+
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert(1, 2, 5);
+        graph.add_edge_or_insert(2, 2, 1);
+        let output = graph.to_edge_list();
+        assert_eq!(output, "1 2 5\n2 2 1\n");
+        let parsed: Graph<String, i32> = Graph::from_edge_list(&output, false).unwrap();
+        assert_eq!(parsed.edge_count(), 2);
+
+        // names that would split or start a comment are quoted
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert("north side", r#"say "hi""#, 2);
+        graph.add_edge_or_insert(r#"say "hi""#, "#1", 3);
+        graph.add_vertex("");
+        let output = graph.to_edge_list();
+        assert_eq!(
+            output,
+            "\"north side\" \"say \\\"hi\\\"\" 2\n\"say \\\"hi\\\"\" \"#1\" 3\n\"\"\n"
+        );
+        let parsed: Graph<String, i32> = Graph::from_edge_list(&output, false).unwrap();
+        assert_eq!(parsed.vertices(), graph.vertices());
+        assert!(parsed.contains_edge(r#"say "hi""#, "#1"));
+        assert_eq!(parsed.to_edge_list(), output);
+    }
+
+    #[test]
+    fn test_adjacency_matrix() {
+        let input = "a b c\n- 4 1\n4 - -\n1 - 0\n";
+        let graph: Graph<String, i32> = Graph::from_adjacency_matrix(input, false).unwrap();
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.contains_edge("c", "c"));
+        let (cost, path) = graph.dijkstra("b".into(), "c".into()).unwrap().unwrap();
+        assert_eq!((cost, path), (5, vec!["b".into(), "a".into(), "c".into()]));
+        assert_eq!(graph.to_adjacency_matrix(), input);
+
+        let input = "x \"y z\"\n- 2\n3 -\n";
+        let graph: Graph<String, i32> = Graph::from_adjacency_matrix(input, true).unwrap();
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.contains_edge("y z", "x"));
+        assert_eq!(graph.to_adjacency_matrix(), input);
+
+        let matrix = |input| Graph::<String, i32>::from_adjacency_matrix(input, false).unwrap_err();
+        let err = matrix("a b\n- 1\n2 -\n");
+        assert_eq!(err.msg(), "matrix is not symmetric");
+        assert_eq!((err.line(), err.column()), (Some(3), Some(1)));
+        let err = matrix("a b\n- 1 1\n");
+        assert_eq!(err.msg(), "expected 2 entries");
+        assert_eq!((err.line(), err.column()), (Some(2), Some(5)));
+        assert_eq!(matrix("a b\n- 1\n").msg(), "expected 2 rows");
+        assert_eq!(matrix("a b\n- 1\n1 -\n- -\n").line(), Some(4));
+        assert_eq!(matrix("a a\n- 1\n1 -\n").column(), Some(3));
+        assert_eq!(matrix("a b\n- one\n1 -\n").column(), Some(3));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert("A", "B", 4);
+        graph.add_edge_or_insert("A", "C", 2);
+        graph.add_edge_or_insert("C", "B", 1);
+        graph.add_vertex("D");
+        let (_, path) = graph.dijkstra("A", "B").unwrap().unwrap();
+        let dot = graph.to_dot_highlighted(&path);
+        assert_eq!(
+            dot,
+            "graph {\n    \"A\" [color=red];\n    \"B\" [color=red];\n    \"C\" [color=red];\n    \"D\";\n    \"A\" -- \"B\" [label=\"4\", cost=\"4\"];\n    \"A\" -- \"C\" [label=\"2\", cost=\"2\", color=red, penwidth=2];\n    \"B\" -- \"C\" [label=\"1\", cost=\"1\", color=red, penwidth=2];\n}\n"
+        );
+
+        let parsed: Graph<String, i32> = Graph::from_dot(&dot).unwrap();
+        assert_eq!(parsed.to_dot(), graph.to_dot());
+
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert(r#"say "hi""#, r"C:\temp\", 1);
+        graph.add_edge_or_insert(r"C:\temp\", r"a\nb", 2);
+        let dot = graph.to_dot();
+        assert!(dot.contains(r#""say \"hi\"" -> "C:\\temp\\""#));
+        let parsed: Graph<String, i32> = Graph::from_dot(&dot).unwrap();
+        assert_eq!(parsed.to_dot(), dot);
+        assert_eq!(parsed.vertices()[0], r#"say "hi""#);
+        let parsed: Graph<String> = Graph::from_dot(r#"graph { "a\nb" -- "\"" }"#).unwrap();
+        assert!(parsed.contains_edge(r"a\nb", "\""));
+
+        // Graphviz would reject these as a `weight`
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert(1, 2, 0.5);
+        graph.add_edge_or_insert(2, 3, -1.5);
+        let dot = graph.to_dot();
+        assert_eq!(
+            dot,
+            "digraph {\n    \"1\";\n    \"2\";\n    \"3\";\n    \"1\" -> \"2\" [label=\"0.5\", cost=\"0.5\"];\n    \"2\" -> \"3\" [label=\"-1.5\", cost=\"-1.5\"];\n}\n"
+        );
+        let parsed: Graph<String, f64> = Graph::from_dot(&dot).unwrap();
+        let paths = parsed.bellman_ford("1".to_string()).unwrap();
+        assert_eq!(paths.distance("3"), Some(-1.0));
+    }
+}
@@ -0,0 +1,363 @@
+use super::{Graph, GraphErr, Weight};
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq)]
+pub struct Matching<T> {
+    pairs: Vec<(T, T)>,
+    unmatched_left: Vec<T>,
+    unmatched_right: Vec<T>,
+}
+
+impl<T> Matching<T> {
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn pairs(&self) -> &[(T, T)] {
+        &self.pairs
+    }
+
+    pub fn unmatched_left(&self) -> &[T] {
+        &self.unmatched_left
+    }
+
+    pub fn unmatched_right(&self) -> &[T] {
+        &self.unmatched_right
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartition().is_ok()
+    }
+
+    pub fn bipartition(&self) -> Result<(Vec<T>, Vec<T>), GraphErr<T>> {
+        self.check_undirected()?;
+        let mut color: Vec<Option<(bool, Option<usize>)>> = vec![None; self.vertex_count()];
+        for root in 0..self.vertex_count() {
+            if color[root].is_some() {
+                continue;
+            }
+            let mut queue = VecDeque::new();
+            color[root] = Some((false, None));
+            queue.push_back(root);
+            while let Some(v) = queue.pop_front() {
+                let side = matches!(color[v], Some((true, _)));
+                for edge in &self.adj_list[v] {
+                    let next_vertex = edge.vertex;
+                    match color[next_vertex] {
+                        None => {
+                            color[next_vertex] = Some((!side, Some(v)));
+                            queue.push_back(next_vertex);
+                        }
+                        Some((next_side, _)) if next_side == side => {
+                            let cycle = odd_cycle(v, next_vertex, &color);
+                            return Err(GraphErr::with_vertices(
+                                "odd cycle",
+                                self.vertices_of(&cycle),
+                            ));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        let mut left = vec![];
+        let mut right = vec![];
+        for (v, vertex) in self.vertices.iter().enumerate() {
+            if matches!(color[v], Some((true, _))) {
+                right.push(vertex.clone());
+            } else {
+                left.push(vertex.clone());
+            }
+        }
+        Ok((left, right))
+    }
+
+    pub fn max_matching(&self) -> Result<Matching<T>, GraphErr<T>> {
+        let (left, _) = self.bipartition()?;
+        self.hopcroft_karp(&left)
+    }
+
+    pub fn hopcroft_karp(&self, left: &[T]) -> Result<Matching<T>, GraphErr<T>> {
+        self.check_undirected()?;
+        let mut is_left = vec![false; self.vertex_count()];
+        for v in left {
+            is_left[self.check_vertex(v)?] = true;
+        }
+        let (left, right): (Vec<usize>, Vec<usize>) =
+            (0..self.vertex_count()).partition(|v| is_left[*v]);
+        // Position of every vertex within its own side.
+        let mut side_index = vec![0; self.vertex_count()];
+        for side in &[&left, &right] {
+            for (i, v) in side.iter().enumerate() {
+                side_index[*v] = i;
+            }
+        }
+
+        let mut adj = vec![vec![]; left.len()];
+        for v in left.iter().chain(right.iter()) {
+            for edge in &self.adj_list[*v] {
+                let next_vertex = edge.vertex;
+                match (is_left[*v], is_left[next_vertex]) {
+                    (true, false) => adj[side_index[*v]].push(side_index[next_vertex]),
+                    (false, true) => {}
+                    _ => {
+                        return Err(GraphErr::with_vertices(
+                            "edge within one side",
+                            self.vertices_of(&[*v, next_vertex]),
+                        ))
+                    }
+                }
+            }
+        }
+        for edges in adj.iter_mut() {
+            edges.sort_unstable();
+            edges.dedup();
+        }
+
+        let mut matcher = HopcroftKarp {
+            adj,
+            match_left: vec![None; left.len()],
+            match_right: vec![None; right.len()],
+            dist: vec![None; left.len()],
+        };
+        while matcher.bfs() {
+            for u in 0..left.len() {
+                if matcher.match_left[u].is_none() {
+                    matcher.dfs(u);
+                }
+            }
+        }
+
+        let mut pairs = vec![];
+        let mut unmatched_left = vec![];
+        for (u, v) in matcher.match_left.iter().enumerate() {
+            let vertex = self.vertices[left[u]].clone();
+            match v {
+                Some(v) => pairs.push((vertex, self.vertices[right[*v]].clone())),
+                None => unmatched_left.push(vertex),
+            }
+        }
+        let unmatched_right = matcher
+            .match_right
+            .iter()
+            .enumerate()
+            .filter(|(_, u)| u.is_none())
+            .map(|(v, _)| self.vertices[right[v]].clone())
+            .collect();
+        Ok(Matching {
+            pairs,
+            unmatched_left,
+            unmatched_right,
+        })
+    }
+}
+
+fn odd_cycle(v1: usize, v2: usize, color: &[Option<(bool, Option<usize>)>]) -> Vec<usize> {
+    // Both ends sit at the same BFS depth parity, so the two tree paths up
+    // to their common ancestor close an odd cycle.
+    let ancestors = |mut v: usize| {
+        let mut path = vec![v];
+        while let Some((_, Some(parent))) = color[v] {
+            path.push(parent);
+            v = parent;
+        }
+        path
+    };
+    let mut path1 = ancestors(v1);
+    let mut path2 = ancestors(v2);
+    while path1.len() > 1 && path2.len() > 1 && path1[path1.len() - 2] == path2[path2.len() - 2] {
+        path1.pop();
+        path2.pop();
+    }
+    if path1.last() == path2.last() {
+        path2.pop();
+    }
+    path1.extend(path2.into_iter().rev());
+    path1
+}
+
+struct HopcroftKarp {
+    adj: Vec<Vec<usize>>,
+    match_left: Vec<Option<usize>>,
+    match_right: Vec<Option<usize>>,
+    dist: Vec<Option<usize>>,
+}
+
+impl HopcroftKarp {
+    fn bfs(&mut self) -> bool {
+        let mut queue = VecDeque::new();
+        for u in 0..self.adj.len() {
+            if self.match_left[u].is_none() {
+                self.dist[u] = Some(0);
+                queue.push_back(u);
+            } else {
+                self.dist[u] = None;
+            }
+        }
+        let mut found = false;
+        while let Some(u) = queue.pop_front() {
+            let next_dist = self.dist[u].map(|d| d + 1);
+            for v in &self.adj[u] {
+                match self.match_right[*v] {
+                    Some(w) => {
+                        if self.dist[w].is_none() {
+                            self.dist[w] = next_dist;
+                            queue.push_back(w);
+                        }
+                    }
+                    None => found = true,
+                }
+            }
+        }
+        found
+    }
+
+    fn dfs(&mut self, root: usize) -> bool {
+        // Each frame holds a left vertex and the index of its next edge, so
+        // the edge it came through sits just before that index.
+        let mut stack = vec![(root, 0)];
+        while let Some((u, i)) = stack.pop() {
+            if i == self.adj[u].len() {
+                self.dist[u] = None;
+                continue;
+            }
+            stack.push((u, i + 1));
+            let v = self.adj[u][i];
+            match self.match_right[v] {
+                None => {
+                    for &(u, next) in &stack {
+                        let v = self.adj[u][next - 1];
+                        self.match_left[u] = Some(v);
+                        self.match_right[v] = Some(u);
+                    }
+                    return true;
+                }
+                Some(w) => {
+                    if self.dist[w] == self.dist[u].map(|d| d + 1) {
+                        stack.push((w, 0));
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bipartition() {
+        let mut graph = Graph::new();
+        for v in 1..=6 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(1, 2, 1).unwrap();
+        graph.add_edge(2, 3, 1).unwrap();
+        graph.add_edge(3, 4, 1).unwrap();
+        graph.add_edge(4, 1, 1).unwrap();
+        graph.add_edge(5, 6, 1).unwrap();
+        assert!(graph.is_bipartite());
+        assert_eq!(graph.bipartition().unwrap(), (vec![1, 3, 5], vec![2, 4, 6]));
+
+        graph.add_edge(4, 5, 1).unwrap();
+        graph.add_edge(6, 4, 1).unwrap();
+        let err = graph.bipartition().unwrap_err();
+        assert_eq!(err.msg(), "odd cycle");
+        assert_eq!(err.vertices(), &[5, 4, 6]);
+
+        graph.remove_edge(&6, &4).unwrap();
+        graph.add_edge(6, 6, 1).unwrap();
+        assert_eq!(graph.bipartition().unwrap_err().vertices(), &[6]);
+        assert!(Graph::<i32>::new_directed().bipartition().is_err());
+    }
+
+    #[test]
+    fn test_odd_cycle_witness() {
+        let mut graph = Graph::new();
+        for v in "abcde".chars() {
+            graph.add_vertex(v);
+        }
+        graph.add_edge('a', 'b', 1).unwrap();
+        graph.add_edge('b', 'c', 1).unwrap();
+        graph.add_edge('c', 'd', 1).unwrap();
+        graph.add_edge('d', 'e', 1).unwrap();
+        graph.add_edge('e', 'a', 1).unwrap();
+        let err = graph.bipartition().unwrap_err();
+        assert_eq!(err.vertices().len(), 5);
+        let cycle = err.vertices();
+        for i in 0..cycle.len() {
+            assert!(graph.contains_edge(&cycle[i], &cycle[(i + 1) % cycle.len()]));
+        }
+    }
+
+    #[test]
+    fn test_hopcroft_karp() {
+        let mut graph = Graph::new();
+        let workers = ["ann", "bob", "cat", "dan", "eve"];
+        let shifts = ["mon", "tue", "wed", "thu"];
+        for v in workers.iter().chain(shifts.iter()) {
+            graph.add_vertex(*v);
+        }
+        graph.add_edge("ann", "mon", 1).unwrap();
+        graph.add_edge("ann", "tue", 1).unwrap();
+        graph.add_edge("bob", "mon", 1).unwrap();
+        graph.add_edge("cat", "tue", 1).unwrap();
+        graph.add_edge("cat", "wed", 1).unwrap();
+        graph.add_edge("dan", "mon", 1).unwrap();
+        graph.add_edge("eve", "wed", 1).unwrap();
+
+        let matching = graph.hopcroft_karp(&workers).unwrap();
+        assert_eq!(matching.len(), 3);
+        assert_eq!(matching.unmatched_right(), &["thu"]);
+        assert_eq!(matching.unmatched_left().len(), 2);
+        for (worker, shift) in matching.pairs() {
+            assert!(graph.contains_edge(worker, shift));
+        }
+
+        graph.add_edge("dan", "thu", 1).unwrap();
+        let matching = graph.hopcroft_karp(&workers).unwrap();
+        assert_eq!(matching.len(), 4);
+        assert!(matching.pairs().contains(&("dan", "thu")));
+        assert_eq!(matching.unmatched_right(), &[] as &[&str]);
+        assert_eq!(graph.max_matching().unwrap().len(), 4);
+
+        graph.add_edge("ann", "bob", 1).unwrap();
+        assert!(graph.hopcroft_karp(&workers).is_err());
+        assert!(graph.hopcroft_karp(&["zed"]).is_err());
+    }
+
+    #[test]
+    fn test_long_augmenting_path() {
+        // Left vertices are even and right ones odd. Every left vertex but 0
+        // first grabs the right vertex just below it, so 0 can only be matched
+        // by shifting the whole chain along one step.
+        let n = 100_000;
+        let mut graph = Graph::new();
+        for v in 0..2 * n + 2 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(0, 1, 1).unwrap();
+        for i in 1..=n {
+            graph.add_edge(2 * i, 2 * i - 1, 1).unwrap();
+            graph.add_edge(2 * i, 2 * i + 1, 1).unwrap();
+        }
+        let left: Vec<_> = (1..=n).chain(0..1).map(|i| 2 * i).collect();
+        let matching = graph.hopcroft_karp(&left).unwrap();
+        assert_eq!(matching.len(), n + 1);
+        assert!(matching.pairs().contains(&(0, 1)));
+        assert!(matching.pairs().contains(&(2 * n, 2 * n + 1)));
+    }
+}
@@ -0,0 +1,697 @@
+pub mod all_pairs;
+pub mod centrality;
+pub mod connectivity;
+pub mod csr;
+pub mod eulerian;
+pub mod flow;
+pub mod generators;
+pub mod hamiltonian;
+pub mod io;
+pub mod matching;
+pub mod shortest_path;
+pub mod spanning_tree;
+pub mod topological;
+pub mod traversal;
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+use num::{Bounded, Num};
+
+#[derive(Debug, PartialEq)]
+pub struct GraphErr<T> {
+    msg: String,
+    vertices: Vec<T>,
+    position: Option<(usize, usize)>,
+}
+
+impl<T> GraphErr<T> {
+    pub fn new(msg: &str) -> Self {
+        GraphErr {
+            msg: msg.to_string(),
+            vertices: vec![],
+            position: None,
+        }
+    }
+
+    pub fn with_vertices(msg: &str, vertices: Vec<T>) -> Self {
+        GraphErr {
+            msg: msg.to_string(),
+            vertices,
+            position: None,
+        }
+    }
+
+    pub fn at(msg: &str, line: usize, column: usize) -> Self {
+        GraphErr {
+            msg: msg.to_string(),
+            vertices: vec![],
+            position: Some((line, column)),
+        }
+    }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn vertices(&self) -> &[T] {
+        &self.vertices
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|(line, _)| line)
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.position.map(|(_, column)| column)
+    }
+}
+
+impl<T: Debug> fmt::Display for GraphErr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg)?;
+        if !self.vertices.is_empty() {
+            write!(f, ": {:?}", self.vertices)?;
+        }
+        if let Some((line, column)) = self.position {
+            write!(f, " at line {}, column {}", line, column)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Error for GraphErr<T> {}
+
+pub trait Weight: Num + Bounded + Copy + PartialOrd + Debug {}
+
+impl<W> Weight for W where W: Num + Bounded + Copy + PartialOrd + Debug {}
+
+// `None` when the sum does not fit in `W`.
+fn checked_add<W: Weight>(a: W, b: W) -> Option<W> {
+    if (b > W::zero() && a > W::max_value() - b) || (b < W::zero() && a < W::min_value() - b) {
+        None
+    } else {
+        Some(a + b)
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphNode<T, W = usize> {
+    vertex: T,
+    weight: W,
+}
+
+impl<T, W: Weight> Ord for GraphNode<T, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .partial_cmp(&other.weight)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T, W: Weight> PartialOrd for GraphNode<T, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, W: Weight> PartialEq for GraphNode<T, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, W: Weight> Eq for GraphNode<T, W> {}
+
+impl<T, W: Weight> GraphNode<T, W> {
+    pub fn new(vertex: T, weight: W) -> Self {
+        GraphNode { vertex, weight }
+    }
+}
+
+// Edges point at internal vertex ids; `Graph::vertices` maps them back.
+#[derive(Debug, Clone)]
+struct Edge<W, E> {
+    vertex: usize,
+    weight: W,
+    data: E,
+}
+
+#[derive(Debug)]
+pub struct Graph<T, W = usize, E = ()> {
+    vertices: Vec<T>,
+    index: HashMap<T, usize>,
+    adj_list: Vec<Vec<Edge<W, E>>>,
+    directed: bool,
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn new() -> Self {
+        Graph::with_edge_data()
+    }
+
+    pub fn new_directed() -> Self {
+        Graph::with_edge_data_directed()
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    // `new` and `new_directed` fix `E = ()`; these two leave it open.
+    pub fn with_edge_data() -> Self {
+        Graph {
+            vertices: vec![],
+            index: HashMap::new(),
+            adj_list: vec![],
+            directed: false,
+        }
+    }
+
+    pub fn with_edge_data_directed() -> Self {
+        Graph {
+            directed: true,
+            ..Graph::with_edge_data()
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn vertices(&self) -> &[T] {
+        &self.vertices
+    }
+
+    pub fn add_vertex(&mut self, vertex: T) {
+        self.insert_vertex(vertex);
+    }
+
+    fn insert_vertex(&mut self, vertex: T) -> usize {
+        if let Some(id) = self.index.get(&vertex) {
+            return *id;
+        }
+        let id = self.vertices.len();
+        self.index.insert(vertex.clone(), id);
+        self.vertices.push(vertex);
+        self.adj_list.push(vec![]);
+        id
+    }
+
+    fn check_vertex(&self, vertex: &T) -> Result<usize, GraphErr<T>> {
+        match self.index.get(vertex) {
+            Some(id) => Ok(*id),
+            None => Err(GraphErr::with_vertices(
+                "vertex not found",
+                vec![vertex.clone()],
+            )),
+        }
+    }
+
+    fn check_undirected(&self) -> Result<(), GraphErr<T>> {
+        if self.directed {
+            Err(GraphErr::new("graph must be undirected"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_directed(&self) -> Result<(), GraphErr<T>> {
+        if self.directed {
+            Ok(())
+        } else {
+            Err(GraphErr::new("graph must be directed"))
+        }
+    }
+
+    fn vertices_of(&self, ids: &[usize]) -> Vec<T> {
+        ids.iter().map(|id| self.vertices[*id].clone()).collect()
+    }
+
+    fn edges(&self, v: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.adj_list[v]
+            .iter()
+            .map(|edge| (edge.vertex, edge.weight))
+    }
+
+    fn unique_edges(&self) -> Vec<(usize, usize, W)> {
+        let mut edges = vec![];
+        for (v, adjacent) in self.adj_list.iter().enumerate() {
+            let mut self_loop = false;
+            for edge in adjacent {
+                if v == edge.vertex && !self.directed {
+                    // Undirected self-loops are stored twice in the same list.
+                    self_loop = !self_loop;
+                    if !self_loop {
+                        continue;
+                    }
+                } else if v > edge.vertex && !self.directed {
+                    continue;
+                }
+                edges.push((v, edge.vertex, edge.weight));
+            }
+        }
+        edges
+    }
+
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.vertex_count()];
+        for edge in self.adj_list.iter().flatten() {
+            in_degrees[edge.vertex] += 1;
+        }
+        in_degrees
+    }
+
+    pub fn add_edge(&mut self, v1: T, v2: T, weight: W) -> Result<(), GraphErr<T>>
+    where
+        E: Clone + Default,
+    {
+        self.add_edge_with(v1, v2, weight, E::default())
+    }
+
+    pub fn add_edge_with(&mut self, v1: T, v2: T, weight: W, data: E) -> Result<(), GraphErr<T>>
+    where
+        E: Clone,
+    {
+        let v1 = self.check_vertex(&v1)?;
+        let v2 = self.check_vertex(&v2)?;
+        self.insert_edge(v1, v2, weight, data);
+        Ok(())
+    }
+
+    pub fn add_edge_or_insert(&mut self, v1: T, v2: T, weight: W)
+    where
+        E: Clone + Default,
+    {
+        let v1 = self.insert_vertex(v1);
+        let v2 = self.insert_vertex(v2);
+        self.insert_edge(v1, v2, weight, E::default());
+    }
+
+    fn insert_edge(&mut self, v1: usize, v2: usize, weight: W, data: E)
+    where
+        E: Clone,
+    {
+        if !self.directed {
+            self.adj_list[v2].push(Edge {
+                vertex: v1,
+                weight,
+                data: data.clone(),
+            });
+        }
+        self.adj_list[v1].push(Edge {
+            vertex: v2,
+            weight,
+            data,
+        });
+    }
+
+    pub fn remove_vertex(&mut self, vertex: &T) -> Result<(), GraphErr<T>> {
+        let id = self.check_vertex(vertex)?;
+        self.index.remove(vertex);
+        self.vertices.remove(id);
+        self.adj_list.remove(id);
+        // Shift the ids above the removed vertex so insertion order is kept.
+        for other in self.index.values_mut() {
+            if *other > id {
+                *other -= 1;
+            }
+        }
+        for edges in &mut self.adj_list {
+            edges.retain(|edge| edge.vertex != id);
+            for edge in edges.iter_mut() {
+                if edge.vertex > id {
+                    edge.vertex -= 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_edge(&mut self, v1: &T, v2: &T) -> Result<W, GraphErr<T>> {
+        let (id1, id2, idx) = self.find_edge(v1, v2)?;
+        let weight = self.adj_list[id1].remove(idx).weight;
+        if let Some(mirror) = self.find_mirror(id1, id2, weight, None) {
+            self.adj_list[id2].remove(mirror);
+        }
+        Ok(weight)
+    }
+
+    pub fn set_weight(&mut self, v1: &T, v2: &T, weight: W) -> Result<W, GraphErr<T>> {
+        let (id1, id2, idx) = self.find_edge(v1, v2)?;
+        let old_weight = std::mem::replace(&mut self.adj_list[id1][idx].weight, weight);
+        if let Some(mirror) = self.find_mirror(id1, id2, old_weight, None) {
+            self.adj_list[id2][mirror].weight = weight;
+        }
+        Ok(old_weight)
+    }
+
+    pub fn edge_data<Q>(&self, v1: &Q, v2: &Q) -> Option<&E>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (id1, _, idx) = self.edge_position(v1, v2)?;
+        Some(&self.adj_list[id1][idx].data)
+    }
+
+    pub fn set_edge_data(&mut self, v1: &T, v2: &T, data: E) -> Result<E, GraphErr<T>>
+    where
+        E: Clone,
+    {
+        let (id1, id2, idx) = self.find_edge(v1, v2)?;
+        let weight = self.adj_list[id1][idx].weight;
+        let skip = if id1 == id2 { Some(idx) } else { None };
+        if let Some(mirror) = self.find_mirror(id1, id2, weight, skip) {
+            self.adj_list[id2][mirror].data = data.clone();
+        }
+        Ok(std::mem::replace(&mut self.adj_list[id1][idx].data, data))
+    }
+
+    pub fn contains_edge<Q>(&self, v1: &Q, v2: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.edge_position(v1, v2).is_some()
+    }
+
+    fn find_edge(&self, v1: &T, v2: &T) -> Result<(usize, usize, usize), GraphErr<T>> {
+        self.check_vertex(v1)?;
+        self.check_vertex(v2)?;
+        self.edge_position(v1, v2)
+            .ok_or_else(|| GraphErr::with_vertices("edge not found", vec![v1.clone(), v2.clone()]))
+    }
+
+    fn edge_position<Q>(&self, v1: &Q, v2: &Q) -> Option<(usize, usize, usize)>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (id1, id2) = (*self.index.get(v1)?, *self.index.get(v2)?);
+        let idx = self.adj_list[id1]
+            .iter()
+            .position(|edge| edge.vertex == id2)?;
+        Some((id1, id2, idx))
+    }
+
+    // The other half of an undirected edge, identified by its weight among
+    // parallel edges. `skip` excludes the first half of a self-loop when it
+    // still matches.
+    fn find_mirror(&self, v1: usize, v2: usize, weight: W, skip: Option<usize>) -> Option<usize> {
+        if self.directed {
+            return None;
+        }
+        (0..self.adj_list[v2].len()).find(|idx| {
+            let edge = &self.adj_list[v2][*idx];
+            Some(*idx) != skip && edge.vertex == v1 && edge.weight == weight
+        })
+    }
+
+    pub fn neighbors<Q>(&self, vertex: &Q) -> Vec<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.index.get(vertex) {
+            Some(id) => self.adj_list[*id]
+                .iter()
+                .map(|edge| self.vertices[edge.vertex].clone())
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        let count = self.adj_list.iter().map(|edges| edges.len()).sum();
+        if self.directed {
+            count
+        } else {
+            count / 2
+        }
+    }
+
+    pub fn out_degree<Q>(&self, vertex: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.index
+            .get(vertex)
+            .map_or(0, |id| self.adj_list[*id].len())
+    }
+
+    pub fn in_degree<Q>(&self, vertex: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        if !self.directed {
+            return self.out_degree(vertex);
+        }
+        match self.index.get(vertex) {
+            Some(id) => self
+                .adj_list
+                .iter()
+                .flatten()
+                .filter(|edge| edge.vertex == *id)
+                .count(),
+            None => 0,
+        }
+    }
+
+    pub fn predecessors<Q>(&self, vertex: &Q) -> Vec<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let id = match self.index.get(vertex) {
+            Some(id) => *id,
+            None => return vec![],
+        };
+        if !self.directed {
+            return self.neighbors(vertex);
+        }
+        let mut data = vec![];
+        for (v, edges) in self.adj_list.iter().enumerate() {
+            if edges.iter().any(|edge| edge.vertex == id) {
+                data.push(self.vertices[v].clone());
+            }
+        }
+        data
+    }
+
+    pub fn reverse(&self) -> Self
+    where
+        E: Clone,
+    {
+        let mut adj_list: Vec<Vec<Edge<W, E>>> = vec![vec![]; self.vertices.len()];
+        for (v, edges) in self.adj_list.iter().enumerate() {
+            for edge in edges {
+                if !self.directed {
+                    adj_list[v].push(edge.clone());
+                } else {
+                    adj_list[edge.vertex].push(Edge {
+                        vertex: v,
+                        ..edge.clone()
+                    });
+                }
+            }
+        }
+        Graph {
+            vertices: self.vertices.clone(),
+            index: self.index.clone(),
+            adj_list,
+            directed: self.directed,
+        }
+    }
+
+    pub fn bfs(&self, start: T) -> Vec<T> {
+        self.bfs_iter(start)
+            .map(|visit| visit.vertex().clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_graph() {
+        let mut graph = Graph::new();
+        let empty: Vec<&str> = vec![];
+        assert_eq!(graph.dfs_recur("A"), empty);
+        assert_eq!(graph.dfs_iter("A").next(), None);
+        assert_eq!(graph.bfs("A"), empty);
+
+        graph.add_vertex("A");
+        graph.add_vertex("B");
+        graph.add_vertex("C");
+        graph.add_vertex("D");
+        graph.add_vertex("E");
+        graph.add_vertex("F");
+        graph.add_edge("A", "B", 4).unwrap();
+        graph.add_edge("A", "C", 2).unwrap();
+        graph.add_edge("B", "E", 3).unwrap();
+        graph.add_edge("C", "D", 2).unwrap();
+        graph.add_edge("C", "F", 4).unwrap();
+        graph.add_edge("D", "E", 3).unwrap();
+        graph.add_edge("D", "F", 1).unwrap();
+        graph.add_edge("E", "F", 1).unwrap();
+        assert_eq!(graph.dfs_recur("A"), vec!["A", "B", "E", "D", "C", "F",]);
+        let dfs: Vec<_> = graph.dfs_iter("A").map(|visit| *visit.vertex()).collect();
+        assert_eq!(dfs, vec!["A", "C", "F", "E", "D", "B"]);
+        assert_eq!(graph.bfs("A"), vec!["A", "B", "C", "E", "D", "F"]);
+        assert_eq!(
+            graph.dijkstra("A", "E"),
+            Ok(Some((6, vec!["A", "C", "D", "F", "E"])))
+        );
+    }
+
+    #[test]
+    fn test_update_graph() {
+        let mut graph = Graph::new();
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        graph.add_vertex(3);
+        assert_eq!(
+            graph.add_edge(1, 4, 10),
+            Err(GraphErr::with_vertices("vertex not found", vec![4]))
+        );
+        graph.add_edge(1, 2, 10).unwrap();
+        graph.add_edge(2, 3, 20).unwrap();
+        graph.add_edge(3, 3, 5).unwrap();
+        graph.add_edge_or_insert(3, 4, 30);
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.neighbors(&3), vec![2, 3, 3, 4]);
+        assert!(graph.contains_edge(&4, &3));
+        assert!(!graph.contains_edge(&1, &3));
+        assert!(!graph.contains_edge(&1, &9));
+
+        assert_eq!(graph.set_weight(&2, &1, 15), Ok(10));
+        assert_eq!(graph.dijkstra(1, 2), Ok(Some((15, vec![1, 2]))));
+        assert!(graph.set_weight(&1, &3, 15).is_err());
+
+        assert_eq!(graph.remove_edge(&3, &3), Ok(5));
+        assert_eq!(graph.neighbors(&3), vec![2, 4]);
+        assert_eq!(graph.remove_edge(&2, &1), Ok(15));
+        assert!(graph.remove_edge(&2, &1).is_err());
+        assert_eq!(graph.neighbors(&1), vec![]);
+        assert_eq!(graph.edge_count(), 2);
+
+        assert_eq!(graph.remove_vertex(&3), Ok(()));
+        assert!(graph.remove_vertex(&3).is_err());
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.neighbors(&4), vec![]);
+
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert('a', 'b', 1);
+        graph.add_edge_or_insert('b', 'a', 2);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.remove_edge(&'a', &'b'), Ok(1));
+        assert!(graph.contains_edge(&'b', &'a'));
+        assert_eq!(graph.remove_vertex(&'a'), Ok(()));
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_directed_graph() {
+        let mut graph = Graph::new_directed();
+        graph.add_vertex("A");
+        graph.add_vertex("B");
+        graph.add_vertex("C");
+        graph.add_vertex("D");
+        graph.add_edge("A", "B", 1).unwrap();
+        graph.add_edge("A", "C", 4).unwrap();
+        graph.add_edge("B", "C", 1).unwrap();
+        graph.add_edge("C", "D", 1).unwrap();
+        graph.add_edge("D", "B", 5).unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.out_degree("A"), 2);
+        assert_eq!(graph.in_degree("A"), 0);
+        assert_eq!(graph.in_degree("B"), 2);
+        assert_eq!(graph.in_degree("C"), 2);
+        assert_eq!(graph.predecessors("B"), vec!["A", "D"]);
+        assert_eq!(graph.dfs_recur("A"), vec!["A", "B", "C", "D"]);
+        assert_eq!(graph.dfs_recur("D"), vec!["D", "B", "C"]);
+        let dfs: Vec<_> = graph.dfs_iter("D").map(|visit| *visit.vertex()).collect();
+        assert_eq!(dfs, vec!["D", "B", "C"]);
+        assert_eq!(graph.bfs("B"), vec!["B", "C", "D"]);
+        assert_eq!(
+            graph.dijkstra("A", "D"),
+            Ok(Some((3, vec!["A", "B", "C", "D"])))
+        );
+        assert_eq!(graph.dijkstra("D", "A"), Ok(None));
+
+        let reversed = graph.reverse();
+        assert_eq!(reversed.out_degree("A"), 0);
+        assert_eq!(reversed.in_degree("A"), 2);
+        assert_eq!(reversed.predecessors("A"), vec!["B", "C"]);
+        assert_eq!(reversed.bfs("D"), vec!["D", "C", "A", "B"]);
+        assert_eq!(reversed.bfs("A"), vec!["A"]);
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Station {
+        name: String,
+        zone: u8,
+    }
+
+    #[test]
+    fn test_owned_vertices_and_edge_data() {
+        let station = |name: &str, zone| Station {
+            name: name.to_string(),
+            zone,
+        };
+        let (a, b, c) = (
+            station("Aldgate", 1),
+            station("Bow", 2),
+            station("Cheam", 5),
+        );
+        let mut graph: Graph<Station, u32, &str> = Graph::with_edge_data();
+        for v in &[&a, &b, &c] {
+            graph.add_vertex((*v).clone());
+        }
+        graph.add_edge_with(a.clone(), b.clone(), 4, "red").unwrap();
+        graph
+            .add_edge_with(b.clone(), c.clone(), 3, "blue")
+            .unwrap();
+        graph.add_edge(a.clone(), c.clone(), 10).unwrap();
+        assert_eq!(graph.edge_data(&b, &a), Some(&"red"));
+        assert_eq!(graph.edge_data(&a, &c), Some(&""));
+        assert_eq!(graph.set_edge_data(&c, &b, "green"), Ok("blue"));
+        assert_eq!(graph.edge_data(&b, &c), Some(&"green"));
+        let path = graph.dijkstra(a.clone(), c.clone()).unwrap();
+        assert_eq!(path, Some((7, graph.vertices().to_vec())));
+
+        graph.remove_vertex(&b).unwrap();
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edge_data(&a, &b), None);
+        assert!(graph.set_edge_data(&a, &b, "gone").is_err());
+        assert_eq!(graph.neighbors(&c), vec![a]);
+
+        let mut graph: Graph<String> = Graph::new();
+        let x = "x".to_string();
+        graph.add_edge_or_insert(x.clone(), x.clone(), 1);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.remove_edge(&x, &x), Ok(1));
+        assert_eq!(graph.neighbors("x"), Vec::<String>::new());
+    }
+}
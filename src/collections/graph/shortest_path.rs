@@ -0,0 +1,565 @@
+use super::{checked_add, Graph, GraphErr, GraphNode, Weight};
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+// Distances and predecessors indexed by vertex id.
+pub(super) type Tree<W> = (Vec<Option<W>>, Vec<Option<usize>>);
+
+#[derive(Debug)]
+pub struct ShortestPaths<T, W = usize> {
+    start: T,
+    distances: HashMap<T, W>,
+    prev: HashMap<T, T>,
+}
+
+impl<T, W> ShortestPaths<T, W>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    pub fn distances(&self) -> &HashMap<T, W> {
+        &self.distances
+    }
+
+    pub fn predecessors(&self) -> &HashMap<T, T> {
+        &self.prev
+    }
+
+    pub fn distance<Q>(&self, vertex: &Q) -> Option<W>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.distances.get(vertex).copied()
+    }
+
+    pub fn path_to<Q>(&self, vertex: &Q) -> Option<Vec<T>>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (vertex, _) = self.distances.get_key_value(vertex)?;
+        let mut path = vec![vertex.clone()];
+        while let Some(prev) = self.prev.get::<T>(&path[path.len() - 1]) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[derive(Debug)]
+pub struct AStarResult<T, W = usize> {
+    path: Option<(W, Vec<T>)>,
+    expanded: usize,
+}
+
+impl<T, W: Weight> AStarResult<T, W> {
+    pub fn cost(&self) -> Option<W> {
+        self.path.as_ref().map(|(cost, _)| *cost)
+    }
+
+    pub fn path(&self) -> Option<&[T]> {
+        self.path.as_ref().map(|(_, path)| &path[..])
+    }
+
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    pub fn into_path(self) -> Option<(W, Vec<T>)> {
+        self.path
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn dijkstra(&self, start: T, end: T) -> Result<Option<(W, Vec<T>)>, GraphErr<T>> {
+        let start = self.check_vertex(&start)?;
+        let end = self.check_vertex(&end)?;
+        let (distances, prev) = self.dijkstra_from(start, Some(end))?;
+        Ok(distances[end].map(|dist| (dist, path_ids(&self.vertices, &prev, end))))
+    }
+
+    pub fn dijkstra_all(&self, start: T) -> Result<ShortestPaths<T, W>, GraphErr<T>> {
+        let id = self.check_vertex(&start)?;
+        let (distances, prev) = self.dijkstra_from(id, None)?;
+        Ok(self.shortest_paths(start, &distances, &prev))
+    }
+
+    fn dijkstra_from(&self, start: usize, end: Option<usize>) -> Result<Tree<W>, GraphErr<T>> {
+        dijkstra_tree(&self.vertices, start, end, |v| self.edges(v))
+    }
+
+    pub fn bellman_ford(&self, start: T) -> Result<ShortestPaths<T, W>, GraphErr<T>> {
+        let id = self.check_vertex(&start)?;
+        let n = self.vertex_count();
+        let mut distances: Vec<Option<W>> = vec![None; n];
+        let mut prev: Vec<Option<usize>> = vec![None; n];
+        let mut too_long = vec![false; n];
+        distances[id] = Some(W::zero());
+
+        let mut relaxed = None;
+        for _ in 0..n {
+            relaxed = None;
+            for v in 0..n {
+                let dist = match distances[v] {
+                    Some(dist) => dist,
+                    None => continue,
+                };
+                for (w, weight) in self.edges(v) {
+                    match checked_add(dist, weight) {
+                        Some(new_dist) if is_shorter(new_dist, distances[w]) => {
+                            distances[w] = Some(new_dist);
+                            prev[w] = Some(v);
+                            relaxed = Some(w);
+                        }
+                        Some(_) => {}
+                        // a distance below the range of `W` would be the shortest
+                        None if weight < W::zero() => {
+                            return Err(GraphErr::with_vertices(
+                                "path weight overflows",
+                                self.vertices_of(&[v, w]),
+                            ))
+                        }
+                        None => too_long[w] = true,
+                    }
+                }
+            }
+            if relaxed.is_none() {
+                break;
+            }
+        }
+
+        match relaxed {
+            Some(vertex) => Err(GraphErr::with_vertices(
+                "negative cycle",
+                self.vertices_of(&negative_cycle(vertex, &prev)),
+            )),
+            None => {
+                check_fit(&self.vertices, &distances, &too_long, None, |v| {
+                    self.edges(v)
+                })?;
+                Ok(self.shortest_paths(start, &distances, &prev))
+            }
+        }
+    }
+
+    pub fn a_star<H>(
+        &self,
+        start: T,
+        goal: T,
+        heuristic: H,
+    ) -> Result<AStarResult<T, W>, GraphErr<T>>
+    where
+        H: Fn(&T) -> W,
+    {
+        let start = self.check_vertex(&start)?;
+        let goal = self.check_vertex(&goal)?;
+        let n = self.vertex_count();
+        let mut g_scores: Vec<Option<W>> = vec![None; n];
+        let mut heap: BinaryHeap<Reverse<GraphNode<usize, W>>> = BinaryHeap::new();
+        let mut prev: Vec<Option<usize>> = vec![None; n];
+        let mut closed = vec![false; n];
+        let mut too_long = vec![false; n];
+        let mut expanded = 0;
+
+        g_scores[start] = Some(W::zero());
+        heap.push(Reverse(GraphNode::new(
+            start,
+            heuristic(&self.vertices[start]),
+        )));
+        while let Some(Reverse(nearest_node)) = heap.pop() {
+            let current_vertex = nearest_node.vertex;
+            if current_vertex == goal {
+                return Ok(AStarResult {
+                    path: g_scores[goal].map(|cost| (cost, path_ids(&self.vertices, &prev, goal))),
+                    expanded,
+                });
+            }
+            if closed[current_vertex] {
+                continue;
+            }
+            closed[current_vertex] = true;
+            expanded += 1;
+            let current_g = g_scores[current_vertex].unwrap_or_else(W::zero);
+            for (next_vertex, weight) in self.edges(current_vertex) {
+                if weight < W::zero() {
+                    return Err(GraphErr::with_vertices(
+                        "negative edge weight",
+                        self.vertices_of(&[current_vertex, next_vertex]),
+                    ));
+                }
+                match checked_add(current_g, weight) {
+                    Some(new_g) if is_shorter(new_g, g_scores[next_vertex]) => {
+                        g_scores[next_vertex] = Some(new_g);
+                        prev[next_vertex] = Some(current_vertex);
+                        // the estimate only orders the heap, so it may saturate
+                        let estimate = checked_add(new_g, heuristic(&self.vertices[next_vertex]))
+                            .unwrap_or_else(W::max_value);
+                        heap.push(Reverse(GraphNode::new(next_vertex, estimate)));
+                    }
+                    Some(_) => {}
+                    None => too_long[next_vertex] = true,
+                }
+            }
+        }
+
+        check_fit(&self.vertices, &g_scores, &too_long, Some(goal), |v| {
+            self.edges(v)
+        })?;
+        Ok(AStarResult {
+            path: None,
+            expanded,
+        })
+    }
+
+    fn shortest_paths(
+        &self,
+        start: T,
+        distances: &[Option<W>],
+        prev: &[Option<usize>],
+    ) -> ShortestPaths<T, W> {
+        let vertices = &self.vertices;
+        ShortestPaths {
+            start,
+            distances: distances
+                .iter()
+                .enumerate()
+                .filter_map(|(v, dist)| dist.map(|dist| (vertices[v].clone(), dist)))
+                .collect(),
+            prev: prev
+                .iter()
+                .enumerate()
+                .filter_map(|(v, u)| u.map(|u| (vertices[v].clone(), vertices[u].clone())))
+                .collect(),
+        }
+    }
+}
+
+// Dijkstra over the `(target, weight)` pairs that `edges` yields for each
+// vertex, so `Graph` and `CsrGraph` share it. Stops once `end` is settled.
+pub(super) fn dijkstra_tree<T, W, F, I>(
+    vertices: &[T],
+    start: usize,
+    end: Option<usize>,
+    edges: F,
+) -> Result<Tree<W>, GraphErr<T>>
+where
+    T: Clone,
+    W: Weight,
+    F: Fn(usize) -> I,
+    I: Iterator<Item = (usize, W)>,
+{
+    let mut distances: Vec<Option<W>> = vec![None; vertices.len()];
+    let mut heap: BinaryHeap<Reverse<GraphNode<usize, W>>> = BinaryHeap::new();
+    let mut prev: Vec<Option<usize>> = vec![None; vertices.len()];
+    let mut too_long = vec![false; vertices.len()];
+
+    distances[start] = Some(W::zero());
+    heap.push(Reverse(GraphNode::new(start, W::zero())));
+    while let Some(Reverse(GraphNode {
+        vertex: current_vertex,
+        weight: current_dist,
+    })) = heap.pop()
+    {
+        if matches!(distances[current_vertex], Some(dist) if current_dist > dist) {
+            continue;
+        }
+        if end == Some(current_vertex) {
+            break;
+        }
+        for (next_vertex, weight) in edges(current_vertex) {
+            if weight < W::zero() {
+                return Err(GraphErr::with_vertices(
+                    "negative edge weight",
+                    vec![
+                        vertices[current_vertex].clone(),
+                        vertices[next_vertex].clone(),
+                    ],
+                ));
+            }
+            match checked_add(current_dist, weight) {
+                Some(new_dist) if is_shorter(new_dist, distances[next_vertex]) => {
+                    distances[next_vertex] = Some(new_dist);
+                    prev[next_vertex] = Some(current_vertex);
+                    heap.push(Reverse(GraphNode::new(next_vertex, new_dist)));
+                }
+                Some(_) => {}
+                None => too_long[next_vertex] = true,
+            }
+        }
+    }
+    check_fit(vertices, &distances, &too_long, end, edges)?;
+    Ok((distances, prev))
+}
+
+// A path whose weight does not fit in `W` is skipped like a longer one, as
+// any path that fits is shorter. Only vertices such paths reach and nothing
+// else does, limited to `end` if given, have a distance that does not fit.
+pub(super) fn check_fit<T, W, F, I>(
+    vertices: &[T],
+    distances: &[Option<W>],
+    too_long: &[bool],
+    end: Option<usize>,
+    edges: F,
+) -> Result<(), GraphErr<T>>
+where
+    T: Clone,
+    W: Weight,
+    F: Fn(usize) -> I,
+    I: Iterator<Item = (usize, W)>,
+{
+    let mut unfit: Vec<bool> = too_long
+        .iter()
+        .zip(distances)
+        .map(|(too_long, dist)| *too_long && dist.is_none())
+        .collect();
+    let mut stack: Vec<usize> = (0..unfit.len()).filter(|v| unfit[*v]).collect();
+    while let Some(v) = stack.pop() {
+        for (w, _) in edges(v) {
+            if distances[w].is_none() && !unfit[w] {
+                unfit[w] = true;
+                stack.push(w);
+            }
+        }
+    }
+    let unfit: Vec<T> = (0..unfit.len())
+        .filter(|v| unfit[*v] && (end.is_none() || end == Some(*v)))
+        .map(|v| vertices[v].clone())
+        .collect();
+    if unfit.is_empty() {
+        Ok(())
+    } else {
+        Err(GraphErr::with_vertices("path weight overflows", unfit))
+    }
+}
+
+pub(super) fn path_ids<T: Clone>(vertices: &[T], prev: &[Option<usize>], end: usize) -> Vec<T> {
+    let mut path = vec![end];
+    while let Some(v) = prev[path[path.len() - 1]] {
+        path.push(v);
+    }
+    path.reverse();
+    path.into_iter().map(|v| vertices[v].clone()).collect()
+}
+
+pub(super) fn is_shorter<W: Weight>(new_dist: W, dist: Option<W>) -> bool {
+    match dist {
+        Some(dist) => new_dist < dist,
+        None => true,
+    }
+}
+
+pub(super) fn negative_cycle(relaxed: usize, prev: &[Option<usize>]) -> Vec<usize> {
+    // Walking back |V| predecessors from a vertex relaxed in the last round
+    // is guaranteed to land on the cycle itself.
+    let mut current_vertex = relaxed;
+    for _ in 0..prev.len() {
+        current_vertex = prev[current_vertex].unwrap_or(current_vertex);
+    }
+    let mut cycle = vec![current_vertex];
+    let mut v = prev[current_vertex].unwrap_or(current_vertex);
+    while v != current_vertex {
+        cycle.push(v);
+        v = prev[v].unwrap_or(current_vertex);
+    }
+    cycle.reverse();
+    let min_idx = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap_or(0);
+    cycle.rotate_left(min_idx);
+    cycle
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph() -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for v in &["A", "B", "C", "D", "E", "F", "G"] {
+            graph.add_vertex(*v);
+        }
+        graph.add_edge("A", "B", 4).unwrap();
+        graph.add_edge("A", "C", 2).unwrap();
+        graph.add_edge("B", "E", 3).unwrap();
+        graph.add_edge("C", "D", 2).unwrap();
+        graph.add_edge("C", "F", 4).unwrap();
+        graph.add_edge("D", "E", 3).unwrap();
+        graph.add_edge("D", "F", 1).unwrap();
+        graph.add_edge("E", "F", 1).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let graph = graph();
+        assert_eq!(
+            graph.dijkstra("A", "E"),
+            Ok(Some((6, vec!["A", "C", "D", "F", "E"])))
+        );
+        assert_eq!(graph.dijkstra("A", "A"), Ok(Some((0, vec!["A"]))));
+        assert_eq!(graph.dijkstra("A", "G"), Ok(None));
+        assert_eq!(
+            graph.dijkstra("A", "Z"),
+            Err(GraphErr::with_vertices("vertex not found", vec!["Z"]))
+        );
+        assert!(graph.dijkstra("Z", "A").is_err());
+
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert(1, 2, usize::MAX);
+        graph.add_edge_or_insert(2, 3, 1);
+        assert_eq!(graph.dijkstra(1, 2), Ok(Some((usize::MAX, vec![1, 2]))));
+        graph.add_edge_or_insert(3, 4, 1);
+        let overflow = |v| GraphErr::with_vertices("path weight overflows", vec![v]);
+        assert_eq!(graph.dijkstra(1, 3), Err(overflow(3)));
+        assert_eq!(graph.dijkstra(1, 4), Err(overflow(4)));
+        assert_eq!(graph.dijkstra(1, 2), Ok(Some((usize::MAX, vec![1, 2]))));
+        let err = graph.bellman_ford(1).unwrap_err();
+        assert_eq!(
+            err,
+            GraphErr::with_vertices("path weight overflows", vec![3, 4])
+        );
+        assert_eq!(graph.a_star(1, 3, |_| 0).unwrap_err(), overflow(3));
+        // overflowing past a vertex that is reached some other way is just longer
+        graph.add_edge_or_insert(1, 3, 5);
+        assert_eq!(graph.dijkstra_all(1).unwrap().distance(&4), Some(6));
+        assert_eq!(graph.bellman_ford(1).unwrap().distance(&3), Some(5));
+
+        // the result does not depend on which edge is relaxed first
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert(1, 2, usize::MAX);
+        graph.add_edge_or_insert(2, 3, 1);
+        graph.add_edge_or_insert(1, 4, 1);
+        graph.add_edge_or_insert(4, 3, 1);
+        assert_eq!(graph.bellman_ford(1).unwrap().distance(&3), Some(2));
+        assert_eq!(graph.dijkstra(1, 3), Ok(Some((2, vec![1, 4, 3]))));
+        assert_eq!(graph.a_star(1, 3, |_| 0).unwrap().path.unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let graph = graph();
+        let paths = graph.dijkstra_all("A").unwrap();
+        assert_eq!(paths.start(), &"A");
+        assert_eq!(paths.distance("A"), Some(0));
+        assert_eq!(paths.distance("B"), Some(4));
+        assert_eq!(paths.distance("D"), Some(4));
+        assert_eq!(paths.distance("E"), Some(6));
+        assert_eq!(paths.distance("F"), Some(5));
+        assert_eq!(paths.distance("G"), None);
+        assert_eq!(paths.distances().len(), 6);
+        assert_eq!(paths.path_to("F"), Some(vec!["A", "C", "D", "F"]));
+        assert_eq!(paths.path_to("G"), None);
+        assert_eq!(paths.predecessors().get("A"), None);
+        assert_eq!(paths.predecessors()["E"], "F");
+        assert!(graph.dijkstra_all("Z").is_err());
+    }
+
+    #[test]
+    fn test_dijkstra_float_and_negative_weights() {
+        let mut graph = Graph::new_directed();
+        graph.add_vertex('a');
+        graph.add_vertex('b');
+        graph.add_vertex('c');
+        graph.add_edge('a', 'b', 1.5).unwrap();
+        graph.add_edge('b', 'c', 0.25).unwrap();
+        graph.add_edge('a', 'c', 2.0).unwrap();
+        assert_eq!(
+            graph.dijkstra('a', 'c'),
+            Ok(Some((1.75, vec!['a', 'b', 'c'])))
+        );
+
+        graph.add_edge('c', 'a', -1.0).unwrap();
+        assert_eq!(
+            graph.dijkstra_all('a').unwrap_err(),
+            GraphErr::with_vertices("negative edge weight", vec!['c', 'a'])
+        );
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let mut graph = Graph::new_directed();
+        for v in 0..6 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(0, 1, 4).unwrap();
+        graph.add_edge(0, 2, 5).unwrap();
+        graph.add_edge(1, 2, -3).unwrap();
+        graph.add_edge(2, 3, 4).unwrap();
+        graph.add_edge(3, 1, 2).unwrap();
+        graph.add_edge(4, 5, -10).unwrap();
+        let paths = graph.bellman_ford(0).unwrap();
+        assert_eq!(paths.distance(&1), Some(4));
+        assert_eq!(paths.distance(&2), Some(1));
+        assert_eq!(paths.distance(&3), Some(5));
+        assert_eq!(paths.distance(&4), None);
+        assert_eq!(paths.path_to(&3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(graph.bellman_ford(4).unwrap().distance(&5), Some(-10));
+        assert!(graph.bellman_ford(9).is_err());
+
+        graph.add_edge(3, 1, -2).unwrap();
+        assert_eq!(
+            graph.bellman_ford(0).unwrap_err(),
+            GraphErr::with_vertices("negative cycle", vec![1, 2, 3])
+        );
+        assert!(graph.bellman_ford(4).is_ok());
+
+        let mut graph = Graph::new();
+        graph.add_vertex("x");
+        graph.add_vertex("y");
+        graph.add_edge("x", "y", -0.5).unwrap();
+        let err = graph.bellman_ford("x").unwrap_err();
+        assert_eq!(err.msg(), "negative cycle");
+        assert_eq!(err.vertices(), &["x", "y"]);
+    }
+
+    #[test]
+    fn test_a_star() {
+        let size = 20;
+        let mut graph = Graph::new();
+        for v in 0..size * size {
+            graph.add_vertex(v);
+        }
+        for row in 0..size {
+            for col in 0..size {
+                let v = row * size + col;
+                if col + 1 < size {
+                    graph.add_edge(v, v + 1, 1).unwrap();
+                }
+                if row + 1 < size {
+                    graph.add_edge(v, v + size, 1).unwrap();
+                }
+            }
+        }
+        let goal = size - 1;
+        let manhattan = |v: &i32| v / size + (size - 1 - v % size);
+
+        let informed = graph.a_star(0, goal, manhattan).unwrap();
+        let uninformed = graph.a_star(0, goal, |_| 0).unwrap();
+        let (cost, path) = graph.dijkstra(0, goal).unwrap().unwrap();
+        assert_eq!(informed.cost(), Some(cost));
+        assert_eq!(uninformed.cost(), Some(cost));
+        assert_eq!(informed.path().unwrap().len(), path.len());
+        assert_eq!(informed.expanded(), (size - 1) as usize);
+        assert!(uninformed.expanded() > 10 * informed.expanded());
+
+        let result = graph.a_star(0, 0, manhattan).unwrap();
+        assert_eq!(result.into_path(), Some((0, vec![0])));
+        assert!(graph.a_star(0, -1, manhattan).is_err());
+
+        graph.add_vertex(-1);
+        let result = graph.a_star(0, -1, |_| 0).unwrap();
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.path(), None);
+        assert_eq!(result.expanded(), (size * size) as usize);
+    }
+}
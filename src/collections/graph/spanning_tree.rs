@@ -0,0 +1,159 @@
+use super::{Graph, GraphErr, GraphNode, Weight};
+use crate::collections::disjoint_set::DisjointSet;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+pub type SpanningTree<T, W> = (W, Vec<(T, T, W)>);
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn kruskal(&self) -> Result<SpanningTree<T, W>, GraphErr<T>> {
+        self.check_undirected()?;
+        let mut edges = self.unique_edges();
+        edges.sort_by(|a, b| {
+            a.2.partial_cmp(&b.2)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| (a.0, a.1).cmp(&(b.0, b.1)))
+        });
+
+        let mut set = DisjointSet::new();
+        for v in 0..self.vertex_count() {
+            set.make_set(v);
+        }
+        let mut total = W::zero();
+        let mut tree = vec![];
+        for (v1, v2, weight) in edges {
+            if set.union(&v1, &v2) {
+                total = total + weight;
+                tree.push((v1, v2, weight));
+            }
+        }
+        Ok((total, self.tree_edges(&tree)))
+    }
+
+    pub fn prim(&self, start: T) -> Result<SpanningTree<T, W>, GraphErr<T>> {
+        self.check_undirected()?;
+        let start = self.check_vertex(&start)?;
+        let mut visited = vec![false; self.vertex_count()];
+        let mut total = W::zero();
+        let mut tree = vec![];
+
+        for root in Some(start).into_iter().chain(0..self.vertex_count()) {
+            if visited[root] {
+                continue;
+            }
+            let mut best: Vec<Option<(W, usize)>> = vec![None; self.vertex_count()];
+            let mut heap: BinaryHeap<Reverse<GraphNode<usize, W>>> = BinaryHeap::new();
+            heap.push(Reverse(GraphNode::new(root, W::zero())));
+            while let Some(Reverse(GraphNode {
+                vertex: current_vertex,
+                weight,
+            })) = heap.pop()
+            {
+                if visited[current_vertex] {
+                    continue;
+                }
+                visited[current_vertex] = true;
+                if let Some((_, from)) = best[current_vertex] {
+                    total = total + weight;
+                    tree.push((from, current_vertex, weight));
+                }
+                for next_node in &self.adj_list[current_vertex] {
+                    if visited[next_node.vertex] {
+                        continue;
+                    }
+                    let is_lighter = match best[next_node.vertex] {
+                        Some((best_weight, _)) => next_node.weight < best_weight,
+                        None => true,
+                    };
+                    if is_lighter {
+                        best[next_node.vertex] = Some((next_node.weight, current_vertex));
+                        heap.push(Reverse(GraphNode::new(next_node.vertex, next_node.weight)));
+                    }
+                }
+            }
+        }
+        Ok((total, self.tree_edges(&tree)))
+    }
+
+    fn tree_edges(&self, tree: &[(usize, usize, W)]) -> Vec<(T, T, W)> {
+        tree.iter()
+            .map(|(v1, v2, weight)| {
+                (
+                    self.vertices[*v1].clone(),
+                    self.vertices[*v2].clone(),
+                    *weight,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph() -> Graph<char, i32> {
+        let mut graph = Graph::new();
+        for v in "ABCDEFGXY".chars() {
+            graph.add_vertex(v);
+        }
+        graph.add_edge('A', 'B', 7).unwrap();
+        graph.add_edge('A', 'D', 5).unwrap();
+        graph.add_edge('B', 'C', 8).unwrap();
+        graph.add_edge('B', 'D', 9).unwrap();
+        graph.add_edge('B', 'E', 7).unwrap();
+        graph.add_edge('C', 'E', 5).unwrap();
+        graph.add_edge('D', 'E', 15).unwrap();
+        graph.add_edge('D', 'F', 6).unwrap();
+        graph.add_edge('E', 'F', 8).unwrap();
+        graph.add_edge('E', 'G', 9).unwrap();
+        graph.add_edge('F', 'G', 11).unwrap();
+        graph.add_edge('X', 'Y', -2).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_kruskal() {
+        let (total, tree) = graph().kruskal().unwrap();
+        assert_eq!(total, 37);
+        assert_eq!(
+            tree,
+            vec![
+                ('X', 'Y', -2),
+                ('A', 'D', 5),
+                ('C', 'E', 5),
+                ('D', 'F', 6),
+                ('A', 'B', 7),
+                ('B', 'E', 7),
+                ('E', 'G', 9),
+            ]
+        );
+        assert!(Graph::<i32>::new_directed().kruskal().is_err());
+    }
+
+    #[test]
+    fn test_prim() {
+        let graph = graph();
+        let (total, tree) = graph.prim('D').unwrap();
+        assert_eq!(total, 37);
+        assert_eq!(
+            tree,
+            vec![
+                ('D', 'A', 5),
+                ('D', 'F', 6),
+                ('A', 'B', 7),
+                ('B', 'E', 7),
+                ('E', 'C', 5),
+                ('E', 'G', 9),
+                ('X', 'Y', -2),
+            ]
+        );
+        assert_eq!(graph.prim('Y').unwrap().0, 37);
+        assert!(graph.prim('Z').is_err());
+    }
+}
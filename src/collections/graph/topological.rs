@@ -0,0 +1,150 @@
+use super::{Graph, GraphErr, Weight};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn topological_sort(&self) -> Result<Vec<T>, GraphErr<T>> {
+        Ok(self.topological_layers()?.into_iter().flatten().collect())
+    }
+
+    pub fn topological_sort_lexicographic(&self) -> Result<Vec<T>, GraphErr<T>>
+    where
+        T: Ord,
+    {
+        self.check_directed()?;
+        let mut in_degrees = self.in_degrees();
+        let mut heap: BinaryHeap<Reverse<(&T, usize)>> = (0..self.vertex_count())
+            .filter(|v| in_degrees[*v] == 0)
+            .map(|v| Reverse((&self.vertices[v], v)))
+            .collect();
+        let mut order = Vec::with_capacity(in_degrees.len());
+        while let Some(Reverse((_, v))) = heap.pop() {
+            order.push(v);
+            for edge in &self.adj_list[v] {
+                in_degrees[edge.vertex] -= 1;
+                if in_degrees[edge.vertex] == 0 {
+                    heap.push(Reverse((&self.vertices[edge.vertex], edge.vertex)));
+                }
+            }
+        }
+        self.check_acyclic(order.len())?;
+        Ok(self.vertices_of(&order))
+    }
+
+    pub fn topological_layers(&self) -> Result<Vec<Vec<T>>, GraphErr<T>> {
+        self.check_directed()?;
+        let mut in_degrees = self.in_degrees();
+        let mut layer: Vec<usize> = (0..self.vertex_count())
+            .filter(|v| in_degrees[*v] == 0)
+            .collect();
+        let mut layers = vec![];
+        let mut count = 0;
+        while !layer.is_empty() {
+            let mut next_layer = vec![];
+            for v in &layer {
+                for edge in &self.adj_list[*v] {
+                    in_degrees[edge.vertex] -= 1;
+                    if in_degrees[edge.vertex] == 0 {
+                        next_layer.push(edge.vertex);
+                    }
+                }
+            }
+            next_layer.sort_unstable();
+            count += layer.len();
+            layers.push(self.vertices_of(&layer));
+            layer = next_layer;
+        }
+        self.check_acyclic(count)?;
+        Ok(layers)
+    }
+
+    fn check_acyclic(&self, count: usize) -> Result<(), GraphErr<T>> {
+        if count == self.vertex_count() {
+            return Ok(());
+        }
+        // The vertices left over also include everything a cycle leads to,
+        // so name one cycle instead.
+        Err(GraphErr::with_vertices(
+            "graph contains a cycle",
+            self.find_cycle().unwrap_or_default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph() -> Graph<&'static str> {
+        let mut graph = Graph::new_directed();
+        for v in &[
+            "shirt", "tie", "jacket", "belt", "pants", "shoes", "socks", "watch",
+        ] {
+            graph.add_vertex(*v);
+        }
+        graph.add_edge("shirt", "tie", 1).unwrap();
+        graph.add_edge("tie", "jacket", 1).unwrap();
+        graph.add_edge("shirt", "belt", 1).unwrap();
+        graph.add_edge("belt", "jacket", 1).unwrap();
+        graph.add_edge("pants", "belt", 1).unwrap();
+        graph.add_edge("pants", "shoes", 1).unwrap();
+        graph.add_edge("socks", "shoes", 1).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = graph();
+        assert_eq!(
+            graph.topological_sort().unwrap(),
+            vec!["shirt", "pants", "socks", "watch", "tie", "belt", "shoes", "jacket"]
+        );
+        assert_eq!(
+            graph.topological_sort_lexicographic().unwrap(),
+            vec!["pants", "shirt", "belt", "socks", "shoes", "tie", "jacket", "watch"]
+        );
+        assert_eq!(
+            graph.topological_layers().unwrap(),
+            vec![
+                vec!["shirt", "pants", "socks", "watch"],
+                vec!["tie", "belt", "shoes"],
+                vec!["jacket"],
+            ]
+        );
+        assert!(Graph::<i32>::new().topological_sort().is_err());
+        assert_eq!(Graph::<i32>::new_directed().topological_sort(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let mut graph = graph();
+        graph.add_vertex("hat");
+        graph.add_edge("jacket", "hat", 1).unwrap();
+        graph.add_edge("hat", "tie", 1).unwrap();
+        let err = GraphErr::with_vertices("graph contains a cycle", vec!["tie", "jacket", "hat"]);
+        assert_eq!(graph.topological_sort(), Err(err));
+        assert_eq!(
+            graph
+                .topological_sort_lexicographic()
+                .unwrap_err()
+                .vertices(),
+            &["tie", "jacket", "hat"]
+        );
+        assert!(graph.topological_layers().is_err());
+
+        // a tail hanging off the cycle is never sorted, but is not on it
+        graph.add_vertex("scarf");
+        graph.add_edge("hat", "scarf", 1).unwrap();
+        let err = GraphErr::with_vertices("graph contains a cycle", vec!["tie", "jacket", "hat"]);
+        assert_eq!(graph.topological_sort(), Err(err));
+        assert_eq!(
+            graph.topological_layers().unwrap_err().vertices(),
+            &["tie", "jacket", "hat"]
+        );
+    }
+}
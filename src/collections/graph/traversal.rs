@@ -0,0 +1,292 @@
+use super::{Graph, Weight};
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Visit<T> {
+    vertex: T,
+    depth: usize,
+    parent: Option<T>,
+}
+
+impl<T: Copy> Visit<T> {
+    pub fn vertex(&self) -> T {
+        self.vertex
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn parent(&self) -> Option<T> {
+        self.parent
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Order {
+    Breadth,
+    // the last neighbor is visited first, as the stack based `dfs_iter` did
+    Depth,
+    // neighbors in insertion order, as the recursive `dfs_recur` did
+    Recursive,
+}
+
+type Visitor<'g, T> = Box<dyn FnMut(&Visit<&'g T>) -> bool + 'g>;
+
+pub struct Traversal<'g, T, W, E> {
+    graph: &'g Graph<T, W, E>,
+    order: Order,
+    frontier: VecDeque<Visit<usize>>,
+    visited: Vec<bool>,
+    prune: Option<Visitor<'g, T>>,
+}
+
+impl<'g, T, W, E> Traversal<'g, T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    fn new(graph: &'g Graph<T, W, E>, starts: &[T], order: Order) -> Self {
+        let ids: Vec<usize> = starts
+            .iter()
+            .filter_map(|start| graph.index.get(start).copied())
+            .collect();
+        Traversal::with_ids(graph, &ids, order)
+    }
+
+    // Depth first from vertex ids, for walks that stay on ids through
+    // `next_id`.
+    pub(super) fn from_ids(graph: &'g Graph<T, W, E>, starts: &[usize]) -> Self {
+        Traversal::with_ids(graph, starts, Order::Depth)
+    }
+
+    fn with_ids(graph: &'g Graph<T, W, E>, starts: &[usize], order: Order) -> Self {
+        let mut frontier: VecDeque<Visit<usize>> = starts
+            .iter()
+            .map(|id| Visit {
+                vertex: *id,
+                depth: 0,
+                parent: None,
+            })
+            .collect();
+        // the stack pops from the back, so reverse to visit starts in order
+        if order != Order::Breadth {
+            frontier.make_contiguous().reverse();
+        }
+        Traversal {
+            graph,
+            order,
+            frontier,
+            visited: vec![false; graph.vertex_count()],
+            prune: None,
+        }
+    }
+
+    // The subtree below every visit for which `f` returns true is skipped;
+    // the visit itself is still yielded.
+    pub fn prune<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&Visit<&'g T>) -> bool + 'g,
+    {
+        self.prune = Some(Box::new(f));
+        self
+    }
+
+    fn resolve(&self, visit: Visit<usize>) -> Visit<&'g T> {
+        let vertices = &self.graph.vertices;
+        Visit {
+            vertex: &vertices[visit.vertex],
+            depth: visit.depth,
+            parent: visit.parent.map(|parent| &vertices[parent]),
+        }
+    }
+
+    pub(super) fn next_id(&mut self) -> Option<Visit<usize>> {
+        loop {
+            let visit = match self.order {
+                Order::Breadth => self.frontier.pop_front()?,
+                Order::Depth | Order::Recursive => self.frontier.pop_back()?,
+            };
+            if self.visited[visit.vertex] {
+                continue;
+            }
+            self.visited[visit.vertex] = true;
+            let resolved = self.resolve(visit);
+            if let Some(prune) = self.prune.as_mut() {
+                if prune(&resolved) {
+                    return Some(visit);
+                }
+            }
+            let visited = &self.visited;
+            let children = self.graph.adj_list[visit.vertex]
+                .iter()
+                .filter(|edge| !visited[edge.vertex])
+                .map(|edge| Visit {
+                    vertex: edge.vertex,
+                    depth: visit.depth + 1,
+                    parent: Some(visit.vertex),
+                });
+            match self.order {
+                Order::Breadth | Order::Depth => self.frontier.extend(children),
+                Order::Recursive => {
+                    let children: Vec<_> = children.collect();
+                    self.frontier.extend(children.into_iter().rev());
+                }
+            }
+            return Some(visit);
+        }
+    }
+}
+
+impl<'g, T, W, E> Iterator for Traversal<'g, T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    type Item = Visit<&'g T>;
+
+    fn next(&mut self) -> Option<Visit<&'g T>> {
+        let visit = self.next_id()?;
+        Some(self.resolve(visit))
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn bfs_iter(&self, start: T) -> Traversal<'_, T, W, E> {
+        Traversal::new(self, &[start], Order::Breadth)
+    }
+
+    pub fn dfs_iter(&self, start: T) -> Traversal<'_, T, W, E> {
+        Traversal::new(self, &[start], Order::Depth)
+    }
+
+    pub fn dfs_recur(&self, v: T) -> Vec<T> {
+        Traversal::new(self, &[v], Order::Recursive)
+            .map(|visit| visit.vertex().clone())
+            .collect()
+    }
+
+    pub fn bfs_iter_multi(&self, starts: &[T]) -> Traversal<'_, T, W, E> {
+        Traversal::new(self, starts, Order::Breadth)
+    }
+
+    pub fn dfs_iter_multi(&self, starts: &[T]) -> Traversal<'_, T, W, E> {
+        Traversal::new(self, starts, Order::Depth)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chain(len: i32) -> Graph<i32> {
+        let mut graph = Graph::new_directed();
+        graph.add_vertex(0);
+        for v in 1..len {
+            graph.add_edge_or_insert(v - 1, v, 1);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_traversal() {
+        let mut graph = Graph::new();
+        graph.add_edge_or_insert("A", "B", 4);
+        graph.add_edge_or_insert("A", "C", 2);
+        graph.add_edge_or_insert("B", "E", 3);
+        graph.add_edge_or_insert("C", "D", 2);
+        graph.add_edge_or_insert("C", "F", 4);
+        graph.add_edge_or_insert("D", "E", 3);
+        graph.add_edge_or_insert("D", "F", 1);
+        graph.add_edge_or_insert("E", "F", 1);
+
+        let bfs: Vec<_> = graph.bfs_iter("A").collect();
+        let summary: Vec<_> = bfs
+            .iter()
+            .map(|visit| (*visit.vertex(), visit.depth(), visit.parent().copied()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("A", 0, None),
+                ("B", 1, Some("A")),
+                ("C", 1, Some("A")),
+                ("E", 2, Some("B")),
+                ("D", 2, Some("C")),
+                ("F", 2, Some("C")),
+            ]
+        );
+
+        let dfs: Vec<_> = graph
+            .dfs_iter("A")
+            .map(|visit| (*visit.vertex(), visit.depth()))
+            .collect();
+        assert_eq!(
+            dfs,
+            vec![("A", 0), ("C", 1), ("F", 2), ("E", 3), ("D", 4), ("B", 4)]
+        );
+
+        let found = graph.bfs_iter("A").find(|visit| *visit.vertex() == "D");
+        assert_eq!(found.and_then(|visit| visit.parent()), Some(&"C"));
+        assert_eq!(graph.dfs_iter("Z").next(), None);
+    }
+
+    #[test]
+    fn test_traversal_prune_and_multi() {
+        let mut graph = Graph::new_directed();
+        graph.add_edge_or_insert(1, 2, 1);
+        graph.add_edge_or_insert(2, 3, 1);
+        graph.add_edge_or_insert(1, 4, 1);
+        graph.add_edge_or_insert(4, 5, 1);
+        graph.add_edge_or_insert(6, 5, 1);
+        graph.add_vertex(7);
+
+        let pruned: Vec<_> = graph
+            .dfs_iter(1)
+            .prune(|visit| *visit.vertex() == 2)
+            .map(|visit| *visit.vertex())
+            .collect();
+        assert_eq!(pruned, vec![1, 4, 5, 2]);
+        let shallow: Vec<_> = graph
+            .bfs_iter(1)
+            .prune(|visit| visit.depth() == 1)
+            .map(|visit| *visit.vertex())
+            .collect();
+        assert_eq!(shallow, vec![1, 2, 4]);
+
+        let multi: Vec<_> = graph
+            .bfs_iter_multi(&[6, 1, 9])
+            .map(|visit| (*visit.vertex(), visit.depth()))
+            .collect();
+        assert_eq!(multi, vec![(6, 0), (1, 0), (5, 1), (2, 1), (4, 1), (3, 2)]);
+        let forest: Vec<_> = graph
+            .dfs_iter_multi(&[7, 4, 1])
+            .map(|visit| (*visit.vertex(), visit.parent().copied()))
+            .collect();
+        assert_eq!(
+            forest,
+            vec![
+                (7, None),
+                (4, None),
+                (5, Some(4)),
+                (1, None),
+                (2, Some(1)),
+                (3, Some(2))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_traversal_long_chain() {
+        let graph = chain(200_000);
+        assert_eq!(graph.dfs_recur(0).len(), 200_000);
+        let last = graph.dfs_iter(0).last().unwrap();
+        assert_eq!((*last.vertex(), last.depth()), (199_999, 199_999));
+        assert_eq!(graph.bfs_iter(0).take(3).count(), 3);
+    }
+}
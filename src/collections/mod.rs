@@ -1,6 +1,7 @@
 pub mod basic_list;
 pub mod bst;
 pub mod deque;
+pub mod disjoint_set;
 pub mod doubly_linked_list;
 pub mod graph;
 pub mod linked_list;